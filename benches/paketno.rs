@@ -0,0 +1,119 @@
+// primerjava skalarnega računanja s paketnim (cargo bench, z --features parallel še vzporedno)
+use std::hint::black_box;
+use std::time::Instant;
+
+use porazdelitve::*;
+
+const N: usize = 1_000_000;
+const PONOVITVE: usize = 5;
+
+// najboljši čas v nanosekundah na točko
+fn izmeri<F: FnMut()>(mut f: F) -> f64 {
+    (0..PONOVITVE)
+        .map(|_| {
+            let zacetek = Instant::now();
+            f();
+            zacetek.elapsed().as_nanos() as f64 / N as f64
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+fn porocilo(ime: &str, skalarno: f64, paketno: f64, vzporedno: Option<f64>) {
+    print!(
+        "{:<24} skalarno {:6.2} ns  paketno {:6.2} ns ({:.1}x)",
+        ime,
+        skalarno,
+        paketno,
+        skalarno / paketno
+    );
+    if let Some(vzporedno) = vzporedno {
+        print!(
+            "  vzporedno {:6.2} ns ({:.1}x)",
+            vzporedno,
+            skalarno / vzporedno
+        );
+    }
+    println!();
+}
+
+fn zvezna<D: Zvezna + Sync>(ime: &str, d: &D, x: &[f64]) {
+    let mut y = vec![0.0; x.len()];
+
+    let skalarno = izmeri(|| {
+        for (x, y) in x.iter().zip(y.iter_mut()) {
+            *y = d.pdf(*x);
+        }
+        black_box(&y);
+    });
+    let paketno = izmeri(|| {
+        d.pdf_batch(x, &mut y);
+        black_box(&y);
+    });
+    #[cfg(feature = "parallel")]
+    let vzporedno = Some(izmeri(|| {
+        porazdelitev::pdf_vzporedno(d, x, &mut y);
+        black_box(&y);
+    }));
+    #[cfg(not(feature = "parallel"))]
+    let vzporedno = None;
+    porocilo(&format!("{} pdf", ime), skalarno, paketno, vzporedno);
+
+    let skalarno = izmeri(|| {
+        for (x, y) in x.iter().zip(y.iter_mut()) {
+            *y = d.cdf(*x);
+        }
+        black_box(&y);
+    });
+    let paketno = izmeri(|| {
+        d.cdf_batch(x, &mut y);
+        black_box(&y);
+    });
+    #[cfg(feature = "parallel")]
+    let vzporedno = Some(izmeri(|| {
+        porazdelitev::cdf_vzporedno(d, x, &mut y);
+        black_box(&y);
+    }));
+    #[cfg(not(feature = "parallel"))]
+    let vzporedno = None;
+    porocilo(&format!("{} cdf", ime), skalarno, paketno, vzporedno);
+}
+
+fn diskretna<D: Diskretna + Sync>(ime: &str, d: &D, k: &[u64]) {
+    let x: Vec<f64> = k.iter().map(|&k| k as f64).collect();
+    let mut y = vec![0.0; k.len()];
+
+    let skalarno = izmeri(|| {
+        for (k, y) in k.iter().zip(y.iter_mut()) {
+            *y = d.pmf(*k);
+        }
+        black_box(&y);
+    });
+    let paketno = izmeri(|| {
+        d.pmf_batch(k, &mut y);
+        black_box(&y);
+    });
+    porocilo(&format!("{} pmf", ime), skalarno, paketno, None);
+
+    let skalarno = izmeri(|| {
+        for (x, y) in x.iter().zip(y.iter_mut()) {
+            *y = d.cdf(*x);
+        }
+        black_box(&y);
+    });
+    let paketno = izmeri(|| {
+        d.cdf_batch(&x, &mut y);
+        black_box(&y);
+    });
+    porocilo(&format!("{} cdf", ime), skalarno, paketno, None);
+}
+
+fn main() {
+    let mut g = Generator::new(1);
+    let x: Vec<f64> = (0..N).map(|_| 8.0 * g.enakomerno() - 4.0).collect();
+    let k: Vec<u64> = (0..N).map(|_| (30.0 * g.enakomerno()) as u64).collect();
+
+    zvezna("Normalna(0, 1)", &Normalna::new(0.0, 1.0), &x);
+    zvezna("Eksponentna(1)", &Eksponentna::new(1.0), &x);
+    diskretna("Poisson(10)", &Poisson::new(10.0), &k);
+    diskretna("Binomska(30, 0.3)", &Binomska::new(30, 0.3), &k);
+}
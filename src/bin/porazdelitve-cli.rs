@@ -0,0 +1,666 @@
+use std::io::Read;
+use std::process::ExitCode;
+
+use porazdelitve::*;
+
+const UPORABA: &str = "\
+Uporaba: porazdelitve-cli <ukaz> <porazdelitev> [vrednosti...] [možnosti]
+         porazdelitve-cli table <vrsta> <porazdelitev>... [možnosti]
+         porazdelitve-cli table t|chi2 [možnosti]
+
+Ukazi:
+  pdf, pmf, cdf, sf    vrednost funkcije v podanih točkah
+  quantile             kvantili za podane verjetnosti
+  moments              pričakovana vrednost, varianca in standardni odklon
+  sample               naključni vzorec
+  table cdf            tabela F(vrstica + stolpec), npr. tabela Φ(z) za \"Normal(0, 1)\"
+  table quantile       kvantili vseh podanih porazdelitev za verjetnosti iz --p
+  table cumulative     P(X <= k) vseh podanih porazdelitev za k = 0, ..., --k
+  table t              kritične vrednosti Studentove t za prostostne stopnje iz --df
+  table chi2           kritične vrednosti porazdelitve χ² za prostostne stopnje iz --df
+
+Porazdelitev je podana z zapisom, npr. \"Binomska(n=20, p=0.3)\" ali \"Normal(0, 2)\".
+Če vrednosti niso podane, jih program prebere s standardnega vhoda.
+
+Možnosti:
+  --format text|csv|json   oblika izpisa (privzeto text)
+  -n <število>             velikost vzorca za sample (privzeto 1)
+  --seed <število>         seme generatorja za sample
+  -h, --help               izpiše to pomoč
+
+Možnosti za table:
+  --format markdown|csv|latex   oblika tabele (privzeto markdown)
+  --rows od:do:korak            vrstice tabele cdf (privzeto 0:3.9:0.1)
+  --cols od:do:korak            stolpci tabele cdf (privzeto 0:0.09:0.01)
+  --p p1,p2,...                 verjetnosti (privzeto 0.9,0.95,0.975,0.99,0.995)
+  --k <število>                 največji k (privzeto 20)
+  --df od:do:korak              prostostne stopnje tabel t in chi2 (privzeto 1:30:1)
+  --precision <število>         število decimalk (privzeto 4)
+";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum OblikaIzpisa {
+    Besedilo,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Ukaz {
+    Pdf,
+    Pmf,
+    Cdf,
+    Sf,
+    Kvantil,
+    Momenti,
+    Vzorec,
+    Tabela(VrstaTabele),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum VrstaTabele {
+    Cdf,
+    Kvantili,
+    Kumulativna,
+    T,
+    HiKvadrat,
+}
+
+impl Ukaz {
+    // ime ukaza v ukazni vrstici
+    fn ime(self) -> &'static str {
+        match self {
+            Ukaz::Pdf => "pdf",
+            Ukaz::Pmf => "pmf",
+            Ukaz::Cdf => "cdf",
+            Ukaz::Sf => "sf",
+            Ukaz::Kvantil => "quantile",
+            Ukaz::Momenti => "moments",
+            Ukaz::Vzorec => "sample",
+            Ukaz::Tabela(_) => "table",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Argumenti {
+    ukaz: Ukaz,
+    // natanko ena, razen pri tabelah
+    zapisi: Vec<String>,
+    // None pomeni, da vrednosti preberemo s standardnega vhoda
+    vrednosti: Option<Vec<f64>>,
+    oblika: OblikaIzpisa,
+    n: usize,
+    seme: Option<u64>,
+    tabela: MoznostiTabele,
+}
+
+#[derive(Debug)]
+struct MoznostiTabele {
+    oblika: tabele::Oblika,
+    vrstice: (f64, f64, f64),
+    stolpci: (f64, f64, f64),
+    verjetnosti: Vec<f64>,
+    k_max: u64,
+    prostostne_stopnje: (f64, f64, f64),
+    decimalke: usize,
+}
+
+impl Default for MoznostiTabele {
+    fn default() -> Self {
+        Self {
+            oblika: tabele::Oblika::Markdown,
+            vrstice: (0.0, 3.9, 0.1),
+            stolpci: (0.0, 0.09, 0.01),
+            verjetnosti: vec![0.9, 0.95, 0.975, 0.99, 0.995],
+            k_max: 20,
+            prostostne_stopnje: (1.0, 30.0, 1.0),
+            decimalke: 4,
+        }
+    }
+}
+
+// "od:do:korak"
+fn razcleni_razpon(s: &str) -> Result<(f64, f64, f64), String> {
+    let deli: Vec<f64> = s
+        .split(':')
+        .map(razcleni_stevilo)
+        .collect::<Result<_, _>>()?;
+    match deli[..] {
+        [od, konec, korak] if korak > 0.0 && od <= konec => Ok((od, konec, korak)),
+        _ => Err(format!("neveljaven razpon '{}', pričakovan od:do:korak", s)),
+    }
+}
+
+fn razcleni_stevilo(s: &str) -> Result<f64, String> {
+    s.trim()
+        .parse::<f64>()
+        .map_err(|_| format!("neveljavno število '{}'", s))
+}
+
+fn razcleni_argumente(argumenti: &[String]) -> Result<Argumenti, String> {
+    let mut pozicijski = Vec::new();
+    let mut oblika = None;
+    let mut n = 1;
+    let mut seme = None;
+    let mut tabela = MoznostiTabele::default();
+
+    let mut iter = argumenti.iter();
+    while let Some(arg) = iter.next() {
+        let mut vrednost = |ime: &str| {
+            iter.next()
+                .ok_or_else(|| format!("možnost {} potrebuje vrednost", ime))
+        };
+        match arg.as_str() {
+            "--format" | "-f" => oblika = Some(vrednost(arg)?.clone()),
+            "-n" => {
+                n = vrednost(arg)?
+                    .parse()
+                    .map_err(|_| "velikost vzorca mora biti nenegativno celo število".to_string())?
+            }
+            "--seed" => {
+                seme = Some(
+                    vrednost(arg)?
+                        .parse()
+                        .map_err(|_| "seme mora biti nenegativno celo število".to_string())?,
+                )
+            }
+            "--rows" => tabela.vrstice = razcleni_razpon(vrednost(arg)?)?,
+            "--cols" => tabela.stolpci = razcleni_razpon(vrednost(arg)?)?,
+            "--p" => {
+                tabela.verjetnosti = vrednost(arg)?
+                    .split(',')
+                    .map(razcleni_stevilo)
+                    .collect::<Result<_, _>>()?;
+                if !tabela.verjetnosti.iter().all(|p| (0.0..=1.0).contains(p)) {
+                    return Err("verjetnosti morajo biti na intervalu [0, 1]".to_string());
+                }
+            }
+            "--k" => {
+                tabela.k_max = vrednost(arg)?
+                    .parse()
+                    .map_err(|_| "k mora biti nenegativno celo število".to_string())?
+            }
+            "--df" => {
+                tabela.prostostne_stopnje = razcleni_razpon(vrednost(arg)?)?;
+                if tabela.prostostne_stopnje.0 <= 0.0 {
+                    return Err("prostostne stopnje morajo biti pozitivne".to_string());
+                }
+            }
+            "--precision" => {
+                tabela.decimalke = vrednost(arg)?.parse().map_err(|_| {
+                    "število decimalk mora biti nenegativno celo število".to_string()
+                })?
+            }
+            _ if arg.starts_with("--") => return Err(format!("neznana možnost '{}'", arg)),
+            _ => pozicijski.push(arg.clone()),
+        }
+    }
+
+    if pozicijski.len() < 2 {
+        return Err("manjkata ukaz in porazdelitev".to_string());
+    }
+    let ukaz = match pozicijski[0].as_str() {
+        "pdf" => Ukaz::Pdf,
+        "pmf" => Ukaz::Pmf,
+        "cdf" => Ukaz::Cdf,
+        "sf" => Ukaz::Sf,
+        "quantile" => Ukaz::Kvantil,
+        "moments" => Ukaz::Momenti,
+        "sample" => Ukaz::Vzorec,
+        "table" => Ukaz::Tabela(match pozicijski[1].as_str() {
+            "cdf" => VrstaTabele::Cdf,
+            "quantile" => VrstaTabele::Kvantili,
+            "cumulative" => VrstaTabele::Kumulativna,
+            "t" => VrstaTabele::T,
+            "chi2" => VrstaTabele::HiKvadrat,
+            drugo => return Err(format!("neznana vrsta tabele '{}'", drugo)),
+        }),
+        drugo => return Err(format!("neznan ukaz '{}'", drugo)),
+    };
+
+    if let Ukaz::Tabela(vrsta) = ukaz {
+        tabela.oblika = match oblika.as_deref() {
+            None | Some("markdown") => tabele::Oblika::Markdown,
+            Some("csv") => tabele::Oblika::Csv,
+            Some("latex") => tabele::Oblika::Latex,
+            Some(drugo) => return Err(format!("neznana oblika tabele '{}'", drugo)),
+        };
+        let zapisi = pozicijski[2..].to_vec();
+        match vrsta {
+            VrstaTabele::T | VrstaTabele::HiKvadrat if !zapisi.is_empty() => {
+                return Err("tabeli t in chi2 ne sprejmeta porazdelitev".to_string());
+            }
+            VrstaTabele::T | VrstaTabele::HiKvadrat => {}
+            _ if zapisi.is_empty() || (vrsta == VrstaTabele::Cdf && zapisi.len() > 1) => {
+                return Err("tabela cdf potrebuje eno porazdelitev, ostale vsaj eno".to_string());
+            }
+            _ => {}
+        }
+        return Ok(Argumenti {
+            ukaz,
+            zapisi,
+            vrednosti: None,
+            oblika: OblikaIzpisa::Besedilo,
+            n,
+            seme,
+            tabela,
+        });
+    }
+
+    let oblika = match oblika.as_deref() {
+        None | Some("text") => OblikaIzpisa::Besedilo,
+        Some("csv") => OblikaIzpisa::Csv,
+        Some("json") => OblikaIzpisa::Json,
+        Some(drugo) => return Err(format!("neznana oblika izpisa '{}'", drugo)),
+    };
+    let vrednosti = if pozicijski.len() > 2 {
+        Some(
+            pozicijski[2..]
+                .iter()
+                .map(|s| razcleni_stevilo(s))
+                .collect::<Result<_, _>>()?,
+        )
+    } else {
+        None
+    };
+
+    Ok(Argumenti {
+        ukaz,
+        zapisi: vec![pozicijski[1].clone()],
+        vrednosti,
+        oblika,
+        n,
+        seme,
+        tabela,
+    })
+}
+
+// običajni decimalni zapis, razen za zelo majhna ali velika števila
+fn stevilo(x: f64) -> String {
+    if x != 0.0 && (x.abs() < 1e-4 || x.abs() >= 1e16) {
+        format!("{:e}", x)
+    } else {
+        format!("{}", x)
+    }
+}
+
+// JSON ne pozna NaN in neskončnosti
+fn json_stevilo(x: f64) -> String {
+    if x.is_finite() {
+        format!("{:?}", x)
+    } else {
+        "null".to_string()
+    }
+}
+
+// ena vrstica izpisa za vsako vhodno vrednost, stolpca imata ime vhoda in ukaza
+fn izpisi_vrstice(oblika: OblikaIzpisa, stolpca: [&str; 2], vrstice: &[(f64, f64)]) -> String {
+    match oblika {
+        OblikaIzpisa::Besedilo => vrstice
+            .iter()
+            .map(|(x, y)| format!("{}({}) = {}\n", stolpca[1], stevilo(*x), stevilo(*y)))
+            .collect(),
+        OblikaIzpisa::Csv => {
+            let mut izpis = format!("{},{}\n", stolpca[0], stolpca[1]);
+            for (x, y) in vrstice {
+                izpis += &format!("{},{}\n", stevilo(*x), stevilo(*y));
+            }
+            izpis
+        }
+        OblikaIzpisa::Json => {
+            let vrstice: Vec<String> = vrstice
+                .iter()
+                .map(|(x, y)| {
+                    format!(
+                        "{{\"{}\":{},\"{}\":{}}}",
+                        stolpca[0],
+                        json_stevilo(*x),
+                        stolpca[1],
+                        json_stevilo(*y)
+                    )
+                })
+                .collect();
+            format!("[{}]\n", vrstice.join(","))
+        }
+    }
+}
+
+fn razcleni_porazdelitev(zapis: &str) -> Result<Box<dyn DynPorazdelitev>, String> {
+    razcleni(zapis).map_err(|e| e.oznaci(zapis))
+}
+
+fn izvedi_tabelo(vrsta: VrstaTabele, argumenti: &Argumenti) -> Result<String, String> {
+    let porazdelitve = argumenti
+        .zapisi
+        .iter()
+        .map(|s| razcleni_porazdelitev(s))
+        .collect::<Result<Vec<_>, _>>()?;
+    let oznake = argumenti.zapisi.iter().map(|s| s.trim().to_string());
+    let moznosti = &argumenti.tabela;
+
+    let tabela = match vrsta {
+        VrstaTabele::Cdf => {
+            let (od, konec, korak) = moznosti.vrstice;
+            let vrstice = tabele::razpon(od, konec, korak);
+            let (od, konec, korak) = moznosti.stolpci;
+            let stolpci = tabele::razpon(od, konec, korak);
+            tabele::mreza_cdf(&*porazdelitve[0], "x", &vrstice, &stolpci)
+        }
+        VrstaTabele::Kvantili => {
+            let porazdelitve: Vec<(String, &dyn Kvantil)> = oznake
+                .zip(&porazdelitve)
+                .map(|(oznaka, d)| (oznaka, &**d as &dyn Kvantil))
+                .collect();
+            tabele::tabela_kvantilov(&porazdelitve, &moznosti.verjetnosti)
+        }
+        VrstaTabele::Kumulativna => {
+            let porazdelitve: Vec<(String, &dyn Porazdelitev)> = oznake
+                .zip(&porazdelitve)
+                .map(|(oznaka, d)| (oznaka, &**d as &dyn Porazdelitev))
+                .collect();
+            tabele::kumulativna_tabela(&porazdelitve, moznosti.k_max)
+        }
+        VrstaTabele::T | VrstaTabele::HiKvadrat => {
+            let (od, konec, korak) = moznosti.prostostne_stopnje;
+            let stopnje = tabele::razpon(od, konec, korak);
+            if vrsta == VrstaTabele::T {
+                tabele::tabela_studentove(&stopnje, &moznosti.verjetnosti)
+            } else {
+                tabele::tabela_hi_kvadrat(&stopnje, &moznosti.verjetnosti)
+            }
+        }
+    };
+
+    Ok(tabela.izpisi(moznosti.oblika, moznosti.decimalke))
+}
+
+fn izvedi(
+    argumenti: Argumenti,
+    vhod: impl FnOnce() -> Result<String, String>,
+) -> Result<String, String> {
+    if let Ukaz::Tabela(vrsta) = argumenti.ukaz {
+        return izvedi_tabelo(vrsta, &argumenti);
+    }
+    let d = razcleni_porazdelitev(&argumenti.zapisi[0])?;
+
+    match argumenti.ukaz {
+        Ukaz::Momenti => {
+            let (e, var) = (d.e(), d.var());
+            let sd = var.sqrt();
+            return Ok(match argumenti.oblika {
+                OblikaIzpisa::Besedilo => format!(
+                    "E = {}\nVar = {}\nsd = {}\n",
+                    stevilo(e),
+                    stevilo(var),
+                    stevilo(sd)
+                ),
+                OblikaIzpisa::Csv => format!(
+                    "mean,variance,sd\n{},{},{}\n",
+                    stevilo(e),
+                    stevilo(var),
+                    stevilo(sd)
+                ),
+                OblikaIzpisa::Json => format!(
+                    "{{\"mean\":{},\"variance\":{},\"sd\":{}}}\n",
+                    json_stevilo(e),
+                    json_stevilo(var),
+                    json_stevilo(sd)
+                ),
+            });
+        }
+        Ukaz::Vzorec => {
+            let mut g = match argumenti.seme {
+                Some(seme) => Generator::new(seme),
+                None => Generator::iz_ure(),
+            };
+            let vzorec = d.vzorci(&mut g, argumenti.n);
+            return Ok(match argumenti.oblika {
+                OblikaIzpisa::Besedilo => vzorec.iter().map(|x| stevilo(*x) + "\n").collect(),
+                OblikaIzpisa::Csv => {
+                    "x\n".to_string()
+                        + &vzorec
+                            .iter()
+                            .map(|x| stevilo(*x) + "\n")
+                            .collect::<String>()
+                }
+                OblikaIzpisa::Json => {
+                    let vzorec: Vec<String> = vzorec.iter().map(|x| json_stevilo(*x)).collect();
+                    format!("[{}]\n", vzorec.join(","))
+                }
+            });
+        }
+        Ukaz::Pdf if d.diskretna() => {
+            return Err(format!("{} je diskretna, uporabi pmf", d.ime()));
+        }
+        Ukaz::Pmf if !d.diskretna() => {
+            return Err(format!("{} je zvezna, uporabi pdf", d.ime()));
+        }
+        _ => {}
+    }
+
+    let vrednosti = match argumenti.vrednosti {
+        Some(vrednosti) => vrednosti,
+        None => vhod()?
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .map(razcleni_stevilo)
+            .collect::<Result<_, _>>()?,
+    };
+
+    let f = |x: f64| -> Result<f64, String> {
+        Ok(match argumenti.ukaz {
+            Ukaz::Pdf | Ukaz::Pmf => d.gostota(x),
+            Ukaz::Cdf => d.cdf(x),
+            Ukaz::Sf => d.sf(x),
+            Ukaz::Kvantil => {
+                if !(0.0..=1.0).contains(&x) {
+                    return Err(format!("verjetnost {} ni na intervalu [0, 1]", x));
+                }
+                d.kvantil(x)
+            }
+            Ukaz::Momenti | Ukaz::Vzorec | Ukaz::Tabela(_) => unreachable!(),
+        })
+    };
+    let vrstice = vrednosti
+        .iter()
+        .map(|&x| f(x).map(|y| (x, y)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let spremenljivka = if argumenti.ukaz == Ukaz::Kvantil {
+        "p"
+    } else {
+        "x"
+    };
+    Ok(izpisi_vrstice(
+        argumenti.oblika,
+        [spremenljivka, argumenti.ukaz.ime()],
+        &vrstice,
+    ))
+}
+
+fn main() -> ExitCode {
+    let argumenti: Vec<String> = std::env::args().skip(1).collect();
+    if argumenti.is_empty() || argumenti.iter().any(|a| a == "-h" || a == "--help") {
+        print!("{}", UPORABA);
+        return ExitCode::SUCCESS;
+    }
+
+    let argumenti = match razcleni_argumente(&argumenti) {
+        Ok(argumenti) => argumenti,
+        Err(napaka) => {
+            eprintln!("napaka: {}\n\n{}", napaka, UPORABA);
+            return ExitCode::from(2);
+        }
+    };
+    let vhod = || {
+        let mut vhod = String::new();
+        std::io::stdin()
+            .read_to_string(&mut vhod)
+            .map_err(|e| format!("branje standardnega vhoda: {}", e))?;
+        Ok(vhod)
+    };
+
+    match izvedi(argumenti, vhod) {
+        Ok(izpis) => {
+            print!("{}", izpis);
+            ExitCode::SUCCESS
+        }
+        Err(napaka) => {
+            eprintln!("napaka: {}", napaka);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cli(argumenti: &[&str], vhod: &str) -> Result<String, String> {
+        let argumenti: Vec<String> = argumenti.iter().map(|s| s.to_string()).collect();
+        let vhod = vhod.to_string();
+        izvedi(razcleni_argumente(&argumenti)?, move || Ok(vhod))
+    }
+
+    #[test]
+    fn test_izpis() {
+        assert_eq!(
+            cli(&["cdf", "Bernoulli(0.25)", "0", "1"], "").unwrap(),
+            "cdf(0) = 0.75\ncdf(1) = 1\n"
+        );
+        assert_eq!(
+            cli(
+                &["pmf", "Binomska(n=2, p=0.5)", "--format", "csv"],
+                "0, 1\n2"
+            )
+            .unwrap(),
+            "x,pmf\n0,0.25\n1,0.5\n2,0.25\n"
+        );
+        assert_eq!(
+            cli(&["quantile", "Uniform(0, 4)", "0.25", "-f", "json"], "").unwrap(),
+            "[{\"p\":0.25,\"quantile\":1.0}]\n"
+        );
+        assert_eq!(
+            cli(&["moments", "Poisson(3)", "--format", "json"], "").unwrap(),
+            format!(
+                "{{\"mean\":3.0,\"variance\":3.0,\"sd\":{:?}}}\n",
+                3f64.sqrt()
+            )
+        );
+        let sample = cli(&["sample", "Normal(0, 1)", "-n", "5", "--seed", "1"], "").unwrap();
+        assert_eq!(sample.lines().count(), 5);
+        assert_eq!(
+            sample,
+            cli(&["sample", "Normal(0, 1)", "-n", "5", "--seed", "1"], "").unwrap()
+        );
+    }
+
+    // cdf diskretnih porazdelitev ne sme seštevati do floor(x) pri velikih x
+    #[test]
+    fn test_velike_vrednosti() {
+        assert_eq!(
+            cli(&["cdf", "Binomska(10, 0.5)", "1e30", "inf"], "").unwrap(),
+            "cdf(1e30) = 1\ncdf(inf) = 1\n"
+        );
+        assert_eq!(
+            cli(&["sf", "Hipergeometrijska(5, 3, 20)", "1e30"], "").unwrap(),
+            "sf(1e30) = 0\n"
+        );
+        for x in ["1e30", "inf"] {
+            let izpis = cli(&["cdf", "Poisson(3)", x], "").unwrap();
+            let y: f64 = izpis.rsplit(' ').next().unwrap().trim().parse().unwrap();
+            assert!((y - 1.0).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_tabele() {
+        let t = cli(
+            &[
+                "table",
+                "cdf",
+                "N(0, 1)",
+                "--rows",
+                "1:1.1:0.1",
+                "--cols",
+                "0:0.01:0.01",
+            ],
+            "",
+        );
+        assert_eq!(
+            t.unwrap(),
+            "| x | 0.00 | 0.01 |\n|---|---:|---:|\n| 1.0 | 0.8413 | 0.8438 |\n| 1.1 | 0.8643 | 0.8665 |\n"
+        );
+        let t = cli(
+            &[
+                "table",
+                "cumulative",
+                "Poisson(1)",
+                "Poisson(2)",
+                "--k",
+                "1",
+                "--format",
+                "csv",
+                "--precision",
+                "3",
+            ],
+            "",
+        );
+        assert_eq!(
+            t.unwrap(),
+            "k,Poisson(1),Poisson(2)\n0,0.368,0.135\n1,0.736,0.406\n"
+        );
+        let t = cli(
+            &[
+                "table", "quantile", "N(0, 1)", "--p", "0.975", "--format", "latex",
+            ],
+            "",
+        )
+        .unwrap();
+        assert!(t.contains("N(0, 1) & 1.9600 \\\\"));
+        let t = cli(
+            &[
+                "table", "quantile", "N(0, 1)", "--p", "0.5", "--format", "csv",
+            ],
+            "",
+        )
+        .unwrap();
+        assert_eq!(t, "p,0.5\n\"N(0, 1)\",0.0000\n");
+        let t = cli(
+            &[
+                "table",
+                "t",
+                "--df",
+                "1:2:1",
+                "--p",
+                "0.975",
+                "--precision",
+                "3",
+            ],
+            "",
+        );
+        assert_eq!(
+            t.unwrap(),
+            "| n | 0.975 |\n|---|---:|\n| 1 | 12.706 |\n| 2 | 4.303 |\n"
+        );
+        let t = cli(&["table", "chi2", "--df", "10:10:1", "--format", "csv"], "");
+        assert_eq!(
+            t.unwrap(),
+            "k,0.900,0.950,0.975,0.990,0.995\n10,15.9872,18.3070,20.4832,23.2093,25.1882\n"
+        );
+        assert!(cli(&["table", "t", "N(0, 1)"], "").is_err());
+        assert!(cli(&["table", "chi2", "--df", "0:5:1"], "").is_err());
+        assert!(cli(&["table", "cdf", "N(0, 1)", "--format", "json"], "").is_err());
+    }
+
+    #[test]
+    fn test_napake() {
+        assert!(cli(&["pdf", "Poisson(3)", "1"], "").is_err());
+        assert!(cli(&["quantile", "Poisson(3)", "1.5"], "").is_err());
+        assert!(cli(&["cdf", "Poisson(-3)", "1"], "").is_err());
+        assert!(cli(&["cdf", "Poisson(3)"], "x").is_err());
+        assert!(cli(&["mean", "Poisson(3)"], "").is_err());
+        assert!(cli(&["cdf", "Poisson(3)", "--format", "xml"], "").is_err());
+    }
+}
@@ -1,18 +1,53 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("brez možnosti std je za matematične funkcije potrebna možnost libm");
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", test)))]
+mod prelude;
+#[cfg(not(any(feature = "std", test)))]
+mod realno;
+
+pub mod nakljucno;
 pub mod utils;
 
 pub mod porazdelitev;
+pub mod slike;
+pub mod tabele;
+pub mod testi;
+
+pub use nakljucno::Generator;
 
 pub use porazdelitev::Diskretna;
+pub use porazdelitev::DynPorazdelitev;
+pub use porazdelitev::Kvantil;
 pub use porazdelitev::Porazdelitev;
 pub use porazdelitev::PricakovanaVrednost;
 pub use porazdelitev::Varianca;
+pub use porazdelitev::VerjetnostIntervala;
+pub use porazdelitev::Vkljucenost;
+pub use porazdelitev::Vzorcenje;
 pub use porazdelitev::Zvezna;
 
 pub use porazdelitev::Bernoulli;
 pub use porazdelitev::Binomska;
 pub use porazdelitev::Eksponentna;
+pub use porazdelitev::Empiricna;
 pub use porazdelitev::EnakomernaInterval;
 pub use porazdelitev::Geometrijska;
+pub use porazdelitev::HiKvadrat;
 pub use porazdelitev::Hipergeometrijska;
+pub use porazdelitev::JedrnaOcena;
+pub use porazdelitev::KoncnaDiskretna;
+pub use porazdelitev::Mesanica;
 pub use porazdelitev::Normalna;
+pub use porazdelitev::Okrnjena;
+pub use porazdelitev::Okrnljiva;
 pub use porazdelitev::Poisson;
+pub use porazdelitev::StudentovaT;
+pub use porazdelitev::Transformirana;
+pub use porazdelitev::Vsota;
+
+pub use porazdelitev::razcleni;
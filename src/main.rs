@@ -1,52 +1,225 @@
 use eframe::egui;
-use egui_plot::{Bar, BarChart, Line, Plot, PlotPoints};
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints, Points};
+use porazdelitve::porazdelitev::register::{self, Vnos};
+use porazdelitve::porazdelitev::{
+    Diskretizirana, Jedro, Kriterij, NapakaPriblizka, Polozaji, Prileganje, SirinaPasu,
+    hellingerjeva_razdalja, kolmogorova_razdalja, ks_pas, najboljse_prileganje, napaka_priblizka,
+    pp_tocke, qq_pas, qq_tocke, razdalja_totalne_variacije,
+};
+use porazdelitve::slike::{Vrsta, serija, slika_porazdelitve};
+use porazdelitve::testi::{RezultatTesta, test_prileganja};
 use porazdelitve::*;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum DistChoice {
-    Bernoulli,
-    Binomska,
-    Eksponentna,
-    EnakomernaInterval,
-    Geometrijska,
-    Hipergeometrijska,
-    Normalna,
-    Poisson,
-}
-
 struct MyApp {
-    selected: DistChoice,
-
-    p: f64,
-    n: u64,
-    s: u64,
-    r: u64,
-    lambda: f64,
-    a: f64,
-    b: f64,
-    mu: f64,
-    sigma2: f64,
+    // index into register::REGISTER
+    selected: usize,
+    // parameters of every distribution, so switching back keeps the old values
+    params: Vec<Vec<f64>>,
 
     info: String,
+    // text specification of the distribution, e.g. "Binomska(n=20, p=0.3)"
+    spec: String,
+    spec_error: Option<String>,
+
+    // named specifications, one "name = spec" line each in PRESETS_FILE
+    presets: Vec<Preset>,
+    preset_name: String,
+    preset_error: Option<String>,
+
+    theme: egui::ThemePreference,
+
+    calc_mode: CalcMode,
+    // bounds of the interval in the probability calculator
+    calc_a: f64,
+    calc_b: f64,
+    calc_inclusion: Vkljucenost,
+    // probability for the quantile mode
+    calc_p: f64,
+
+    view: View,
+
+    // the current view rendered to SVG, or PNG if the path ends with .png
+    export_path: String,
+    export_status: Option<Result<String, String>>,
+
+    // distributions drawn over the current one for comparison
+    layers: Vec<Layer>,
+    // (i, j, Kolmogorov, total variation, Hellinger) for every pair of layers
+    layer_distances: Vec<(usize, usize, f64, f64, f64)>,
+
+    // a simulated sample drawn over the density, with its goodness-of-fit test
+    sim_n: usize,
+    sim_bins: usize,
+    sim_seed: u64,
+    sim_sample: Option<Vec<f64>>,
+    sim_test: Option<(&'static str, RezultatTesta)>,
+
+    // imported data: (column name, values) and the MLE fits of the chosen column, best AIC first
+    data_path: String,
+    data_columns: Vec<(String, Vec<f64>)>,
+    data_column: usize,
+    data_bins: usize,
+    // the chart of the chosen column, rebuilt only when the column or the bins change
+    data_bars: Option<Vec<Bar>>,
+    data_error: Option<String>,
+    data_fits: Option<Vec<Prileganje>>,
+    // fitting runs in a background thread, the result arrives here
+    data_fitting: Option<std::sync::mpsc::Receiver<Vec<Prileganje>>>,
+    data_criterion: Kriterij,
+
+    // Q-Q and P-P plots of the simulated sample or the chosen data column
+    prob_use_data: bool,
+    prob_positions: Polozaji,
+    prob_alpha: f64,
+
+    // central limit theorem simulator: means of clt_n draws, clt_speed new means per frame
+    clt_n: usize,
+    clt_repetitions: usize,
+    clt_speed: usize,
+    clt_running: bool,
+    clt_means: Vec<f64>,
+    clt_generator: Generator,
+    // the distribution and n the means were drawn for
+    clt_params: Option<(CachedParams, usize)>,
+
+    kde_show: bool,
+    kde_n: usize,
+    kde_jedro: Jedro,
+    kde_sirina: SirinaPasu,
+    kde_seed: u64,
 
     cached_params: Option<CachedParams>,
     cached_plot_data: Option<PlotData>,
+    cached_cdf: Option<Vec<[f64; 2]>>,
+    // None inside means the current distribution has no approximations
+    cached_approx: Option<Option<ApproxData>>,
+    cached_kde: Option<Vec<[f64; 2]>>,
+    cached_probability: Option<Option<ProbabilityData>>,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 struct CachedParams {
-    selected: DistChoice,
-    p: f64,
-    n: u64,
-    s: u64,
-    r: u64,
-    lambda: f64,
-    a: f64,
-    b: f64,
-    mu: f64,
-    sigma2: f64,
+    selected: usize,
+    params: Vec<f64>,
+}
+
+// what survives a restart, kept in eframe's storage under eframe::APP_KEY
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedState {
+    // by register name, so reordering the register keeps the values
+    selected: String,
+    params: Vec<(String, Vec<f64>)>,
+    theme: egui::ThemePreference,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Preset {
+    name: String,
+    // e.g. "Hipergeometrijska(s=4, r=10, n=52)"
+    spec: String,
+}
+
+const APP_ID: &str = "VERJETNOST";
+// in eframe's storage directory, next to the saved state
+const PRESETS_FILE: &str = "prednastavitve.txt";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum CalcMode {
+    // P(a < X < b) with chosen endpoints
+    Interval,
+    // x with P(X <= x) = p
+    Quantile,
+}
+
+struct Layer {
+    distribution: Box<dyn DynPorazdelitev>,
+    // shown in the legend, e.g. "Poisson(lambda=5)"
+    name: String,
+    color: egui::Color32,
+    visible: bool,
+    plot_data: PlotData,
+    cdf: Vec<[f64; 2]>,
+}
+
+impl Layer {
+    fn new(distribution: Box<dyn DynPorazdelitev>, color: egui::Color32) -> Self {
+        let plot_data = plot_data(distribution.as_ref());
+        let cdf = cdf_points(distribution.as_ref(), &plot_data);
+        Self {
+            name: distribution.to_string(),
+            distribution,
+            color,
+            visible: true,
+            plot_data,
+            cdf,
+        }
+    }
 }
 
+const LAYER_COLORS: [egui::Color32; 6] = [
+    egui::Color32::from_rgb(31, 119, 180),
+    egui::Color32::from_rgb(44, 160, 44),
+    egui::Color32::from_rgb(214, 39, 40),
+    egui::Color32::from_rgb(148, 103, 189),
+    egui::Color32::from_rgb(140, 86, 75),
+    egui::Color32::from_rgb(23, 190, 207),
+];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum View {
+    Density,
+    Cdf,
+    // density and cdf next to each other
+    Both,
+    // exact distribution against its Poisson, binomial and normal approximations
+    Approximation,
+    // histogram of sample means against the normal limit
+    Clt,
+    // imported CSV data with fitted distributions
+    Data,
+    // Q-Q and P-P plots of a sample against the current distribution
+    Probability,
+}
+
+// points and confidence bands of the Q-Q and P-P plots
+struct ProbabilityData {
+    qq: Vec<[f64; 2]>,
+    pp: Vec<[f64; 2]>,
+    // lower and upper edge of the band around y = x in the Q-Q plot
+    qq_band: (Vec<[f64; 2]>, Vec<[f64; 2]>),
+    // half width of the band in the P-P plot
+    pp_band: f64,
+}
+
+struct Approximation {
+    name: &'static str,
+    // pmf at the integers of the exact bars
+    points: Vec<[f64; 2]>,
+    error: NapakaPriblizka,
+}
+
+struct ApproxData {
+    approximations: Vec<Approximation>,
+    // density of the normal approximation
+    normal_pdf: Vec<[f64; 2]>,
+    // (name, [size, log10 of the max cdf error]) as the size parameter grows
+    convergence: Vec<(&'static str, Vec<[f64; 2]>)>,
+    // the parameter that grows, e.g. "n"
+    size: &'static str,
+}
+
+const INCLUSIONS: [(Vkljucenost, &str); 4] = [
+    (Vkljucenost::Zaprt, "[a, b]"),
+    (Vkljucenost::Odprt, "(a, b)"),
+    (Vkljucenost::LevoOdprt, "(a, b]"),
+    (Vkljucenost::DesnoOdprt, "[a, b)"),
+];
+
+// the calculator is evaluated every frame, so its interval bounds are kept finite and moderate
+const CALC_RANGE: std::ops::RangeInclusive<f64> = -1e9..=1e9;
+
+const HIGHLIGHT: egui::Color32 = egui::Color32::from_rgb(230, 120, 40);
+
 #[derive(Debug)]
 enum PlotData {
     Bars(Vec<Bar>),
@@ -56,225 +229,1188 @@ enum PlotData {
 impl Default for MyApp {
     fn default() -> Self {
         Self {
-            selected: DistChoice::Bernoulli,
-            p: 0.5,
-            n: 10,
-            s: 5,
-            r: 5,
-            lambda: 1.0,
-            a: 0.0,
-            b: 1.0,
-            mu: 0.0,
-            sigma2: 1.0,
+            selected: 0,
+            params: register::REGISTER
+                .iter()
+                .map(|v| v.privzeti_parametri())
+                .collect(),
             info: String::new(),
+            spec: String::new(),
+            spec_error: None,
+            presets: vec![],
+            preset_name: String::new(),
+            preset_error: None,
+            theme: egui::ThemePreference::System,
+            calc_mode: CalcMode::Interval,
+            calc_a: 0.0,
+            calc_b: 1.0,
+            calc_inclusion: Vkljucenost::Zaprt,
+            calc_p: 0.5,
+            view: View::Density,
+            export_path: "porazdelitev.svg".to_string(),
+            export_status: None,
+            layers: Vec::new(),
+            layer_distances: Vec::new(),
+            sim_n: 1000,
+            sim_bins: 30,
+            sim_seed: 0,
+            sim_sample: None,
+            sim_test: None,
+            data_path: String::new(),
+            data_columns: Vec::new(),
+            data_column: 0,
+            data_bars: None,
+            data_bins: 30,
+            data_error: None,
+            data_fits: None,
+            data_fitting: None,
+            data_criterion: Kriterij::Aic,
+            prob_use_data: false,
+            prob_positions: Polozaji::Blom,
+            prob_alpha: 0.05,
+            clt_n: 10,
+            clt_repetitions: 5000,
+            clt_speed: 20,
+            clt_running: false,
+            clt_means: Vec::new(),
+            clt_generator: Generator::new(0),
+            clt_params: None,
+            kde_show: false,
+            kde_n: 200,
+            kde_jedro: Jedro::Gaussovo,
+            kde_sirina: SirinaPasu::Silverman,
+            kde_seed: 0,
             cached_params: None,
             cached_plot_data: None,
+            cached_cdf: None,
+            cached_approx: None,
+            cached_kde: None,
+            cached_probability: None,
         }
     }
 }
 
 impl MyApp {
+    fn entry(&self) -> &'static Vnos {
+        &register::REGISTER[self.selected]
+    }
+
     fn current_params(&self) -> CachedParams {
         CachedParams {
             selected: self.selected,
-            p: self.p,
-            n: self.n,
-            s: self.s,
-            r: self.r,
-            lambda: self.lambda,
-            a: self.a,
-            b: self.b,
-            mu: self.mu,
-            sigma2: self.sigma2,
+            params: self.params[self.selected].clone(),
         }
     }
 
+    fn distribution(&self) -> Result<Box<dyn DynPorazdelitev>, String> {
+        (self.entry().ustvari)(&self.params[self.selected])
+    }
+
     fn get_plot_data(&mut self) -> &PlotData {
         let current_params = self.current_params();
-        
+
         if self.cached_params.as_ref() != Some(&current_params) {
             self.cached_plot_data = Some(self.calculate_plot_data());
             self.cached_params = Some(current_params);
+            self.cached_kde = None;
+            self.cached_cdf = None;
+            self.cached_approx = None;
+            self.cached_probability = None;
+            self.sim_sample = None;
         }
-        
+
         self.cached_plot_data.as_ref().unwrap()
     }
 
-    fn calculate_plot_data(&self) -> PlotData {
-        match self.selected {
-            DistChoice::Bernoulli => {
-                let bars = vec![
-                    Bar::new(0.0, 1.0 - self.p).width(0.6),
-                    Bar::new(1.0, self.p).width(0.6),
-                ];
-                PlotData::Bars(bars)
-            },
-            DistChoice::Binomska => {
-                let d = Binomska::new(self.n, self.p);
-                let mean = d.e();
-                let std = d.var().sqrt();
-                
-                let x_min = (mean - 3.0 * std).max(0.0) as u64;
-                let x_max = (mean + 3.0 * std).min(self.n as f64) as u64;
-
-                let bars: Vec<Bar> = (x_min..=x_max)
-                    .map(|k| Bar::new(k as f64, d.pmf(k)).width(0.6))
-                    .collect();
+    fn get_cdf_data(&mut self) -> &Vec<[f64; 2]> {
+        // refreshes the caches if the parameters changed
+        self.get_plot_data();
+        if self.cached_cdf.is_none() {
+            let points = match self.distribution() {
+                Ok(d) => cdf_points(d.as_ref(), self.cached_plot_data.as_ref().unwrap()),
+                Err(_) => vec![],
+            };
+            self.cached_cdf = Some(points);
+        }
 
-                PlotData::Bars(bars)
-            },
-            DistChoice::Eksponentna => {
-                let x_max = 4.0 / self.lambda;
+        self.cached_cdf.as_ref().unwrap()
+    }
+
+    fn get_approx_data(&mut self) -> Option<&ApproxData> {
+        // refreshes the caches if the parameters changed
+        self.get_plot_data();
+        if self.cached_approx.is_none() {
+            self.cached_approx = Some(
+                self.distribution()
+                    .ok()
+                    .and_then(|d| approx_data(d.as_ref())),
+            );
+        }
 
-                let points: Vec<[f64; 2]> = (0..=500)
+        self.cached_approx.as_ref().unwrap().as_ref()
+    }
+
+    fn get_kde_data(&mut self) -> &Vec<[f64; 2]> {
+        if self.cached_kde.is_none() {
+            let sample = match self.distribution() {
+                Ok(d) => d.vzorci(&mut Generator::new(self.kde_seed), self.kde_n),
+                Err(_) => vec![],
+            };
+            let points = if sample.iter().all(|&x| x == sample[0]) {
+                // JedrnaOcena::new needs two distinct values to estimate the bandwidth from
+                vec![]
+            } else {
+                let kde = JedrnaOcena::new(&sample, self.kde_jedro, self.kde_sirina);
+                let x_min = kde.kvantil(0.001);
+                let x_max = kde.kvantil(0.999);
+                (0..=500)
                     .map(|i| {
-                        let x = i as f64 / 500.0 * x_max;
-                        let y = self.lambda * (-self.lambda * x).exp();
-                        [x, y]
+                        let x = x_min + (x_max - x_min) * i as f64 / 500.0;
+                        [x, kde.pdf(x)]
                     })
-                    .collect();
+                    .collect()
+            };
+            self.cached_kde = Some(points);
+        }
 
-                PlotData::Line(points)
-            },
-            DistChoice::EnakomernaInterval => {
-                let height = 1.0 / (self.b - self.a);
-                let margin = (self.b - self.a) * 0.1;
-                
-                let points = vec![
-                    [self.a - margin, 0.0],
-                    [self.a, 0.0],
-                    [self.a, height],
-                    [self.b, height],
-                    [self.b, 0.0],
-                    [self.b + margin, 0.0],
-                ];
-                
-                PlotData::Line(points)
-            },
-            DistChoice::Geometrijska => {
-                let d = Geometrijska::new(self.p);
-                let x_max = ((-5.0 / d.q.ln()) - 1.0).ceil() as u64;
-
-                let bars: Vec<Bar> = (0..=x_max)
-                    .filter_map(|k| {
-                        let pmf_val = d.pmf(k);
-                        Some(Bar::new(k as f64, pmf_val).width(0.6))
-                    })
-                    .collect();
+        self.cached_kde.as_ref().unwrap()
+    }
 
-                PlotData::Bars(bars)
-            },
-            DistChoice::Hipergeometrijska => {
-                let x_min = if self.n > self.r { self.n - self.r } else { 0 };
-                let x_max = self.n.min(self.s);
+    fn calculate_plot_data(&self) -> PlotData {
+        match self.distribution() {
+            Ok(d) => plot_data(d.as_ref()),
+            Err(_) => PlotData::Bars(vec![]),
+        }
+    }
 
-                if x_min <= x_max {
-                    let d = Hipergeometrijska::new(self.s, self.r, self.n);
-                    let bars: Vec<Bar> = (x_min..=x_max)
-                        .map(|k| Bar::new(k as f64, d.pmf(k)).width(0.6))
-                        .collect();
-                    PlotData::Bars(bars)
-                } else {
-                    PlotData::Bars(vec![])
+    fn add_layer(&mut self) {
+        if let Ok(d) = self.distribution() {
+            let color = LAYER_COLORS[self.layers.len() % LAYER_COLORS.len()];
+            self.layers.push(Layer::new(d, color));
+            self.update_layer_distances();
+        }
+    }
+
+    fn update_layer_distances(&mut self) {
+        self.layer_distances.clear();
+        for i in 0..self.layers.len() {
+            for j in i + 1..self.layers.len() {
+                let p = self.layers[i].distribution.as_ref();
+                let q = self.layers[j].distribution.as_ref();
+                self.layer_distances.push((
+                    i,
+                    j,
+                    kolmogorova_razdalja(p, q),
+                    razdalja_totalne_variacije(p, q),
+                    hellingerjeva_razdalja(p, q),
+                ));
+            }
+        }
+    }
+
+    // the same figure as the density or cdf plot, with the visible layers
+    fn export(&self) -> Result<String, String> {
+        let d = self.distribution()?;
+        let kind = if self.view == View::Cdf {
+            Vrsta::Cdf
+        } else {
+            Vrsta::Gostota
+        };
+        let mut figure = slika_porazdelitve(d.as_ref(), kind);
+        for layer in self.layers.iter().filter(|l| l.visible) {
+            figure.dodaj(serija(layer.distribution.as_ref(), kind), &layer.name);
+        }
+
+        let bytes = if self.export_path.to_lowercase().ends_with(".png") {
+            #[cfg(feature = "png")]
+            {
+                figure.png()?
+            }
+            #[cfg(not(feature = "png"))]
+            return Err("izvoz v PNG zahteva prevajanje z možnostjo png".to_string());
+        } else {
+            figure.svg().into_bytes()
+        };
+        std::fs::write(&self.export_path, bytes).map_err(|err| err.to_string())?;
+        Ok(format!("shranjeno v {}", self.export_path))
+    }
+
+    fn export_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Datoteka:");
+            ui.text_edit_singleline(&mut self.export_path);
+            if ui.button("Izvozi").clicked() {
+                self.export_status = Some(self.export());
+            }
+            match &self.export_status {
+                Some(Ok(message)) => {
+                    ui.label(message);
                 }
-            },
-            DistChoice::Normalna => {
-                let sigma = self.sigma2.sqrt();
-                let x_min = self.mu - 4.0 * sigma;
-                let x_max = self.mu + 4.0 * sigma;
-
-                let d = Normalna::new(self.mu, self.sigma2);
-                let points: Vec<[f64; 2]> = (0..=500)
-                    .map(|i| {
-                        let x = x_min + (x_max - x_min) * i as f64 / 500.0;
-                        let y = d.pdf(x);
-                        [x, y]
-                    })
-                    .collect();
+                Some(Err(err)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                None => {}
+            }
+        });
+    }
 
-                PlotData::Line(points)
-            },
-            DistChoice::Poisson => {
-                let x_max = (self.lambda + 4.0 * self.lambda.sqrt()).ceil() as u64;
-                
-                let d = Poisson::new(self.lambda);
-                let bars: Vec<Bar> = (0..=x_max)
-                    .filter_map(|k| {
-                        let pmf_val = d.pmf(k);
-                        Some(Bar::new(k as f64, pmf_val).width(0.6))
-                    })
-                    .collect();
-                
-                PlotData::Bars(bars)
+    fn layers_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Sloji:");
+            if ui.button("Dodaj trenutno porazdelitev").clicked() {
+                self.add_layer();
             }
+        });
+
+        let mut removed = None;
+        for (i, layer) in self.layers.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut layer.visible, "");
+                ui.color_edit_button_srgba(&mut layer.color);
+                ui.label(&layer.name);
+                if ui.small_button("✖").clicked() {
+                    removed = Some(i);
+                }
+            });
+        }
+        if let Some(i) = removed {
+            self.layers.remove(i);
+            self.update_layer_distances();
+        }
+
+        if !self.layer_distances.is_empty() {
+            egui::Grid::new("layer_distances")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.label("");
+                    ui.label("Kolmogorov");
+                    ui.label("totalna variacija");
+                    ui.label("Hellinger");
+                    ui.end_row();
+                    for &(i, j, ks, tv, hellinger) in &self.layer_distances {
+                        ui.label(&self.layers[i].name);
+                        ui.label(&self.layers[j].name);
+                        ui.label(format!("{:.4}", ks));
+                        ui.label(format!("{:.4}", tv));
+                        ui.label(format!("{:.4}", hellinger));
+                        ui.end_row();
+                    }
+                });
         }
     }
 
-    fn update_info(&mut self) {
-        self.info = match self.selected {
-            DistChoice::Bernoulli => {
-                let d = Bernoulli::new(self.p);
+    // the highlighted region (a, b, inclusion); the quantile mode highlights (-inf, x_p]
+    fn calc_region(&self, d: &dyn DynPorazdelitev) -> (f64, f64, Vkljucenost) {
+        match self.calc_mode {
+            CalcMode::Interval => (self.calc_a, self.calc_b, self.calc_inclusion),
+            CalcMode::Quantile => (
+                f64::NEG_INFINITY,
+                d.kvantil(self.calc_p),
+                Vkljucenost::Zaprt,
+            ),
+        }
+    }
+
+    fn calc_result(&self) -> String {
+        let d = match self.distribution() {
+            Ok(d) => d,
+            Err(_) => return String::new(),
+        };
+        match self.calc_mode {
+            CalcMode::Interval => {
+                let (a, b, inclusion) = self.calc_region(d.as_ref());
+                let lower = if inclusion.vkljucuje_a() { "≤" } else { "<" };
+                let upper = if inclusion.vkljucuje_b() { "≤" } else { "<" };
                 format!(
-                    "Bernoulli (p={:.3})\nPričakovana vrednost: {:.3}\nVarianca: {:.3}",
-                    self.p, d.e(), d.var()
+                    "P(a {} X {} b) = {:.6}",
+                    lower,
+                    upper,
+                    d.verjetnost_intervala(a, b, inclusion)
                 )
             }
-            DistChoice::Binomska => {
-                let d = Binomska::new(self.n, self.p);
-                format!(
-                    "Binomska (n={}, p={:.3})\nPričakovana vrednost: {:.3}\nVarianca: {:.3}",
-                    self.n, self.p, d.e(), d.var()
-                )
+            CalcMode::Quantile => {
+                let x = d.kvantil(self.calc_p);
+                format!("x = {:.6}, P(X ≤ x) = {:.6}", x, d.cdf(x))
             }
-            DistChoice::Eksponentna => {
-                let d = Eksponentna::new(self.lambda);
-                format!(
-                    "Eksponentna (λ={:.3})\nPričakovana vrednost: {:.3}\nVarianca: {:.3}",
-                    self.lambda, d.e(), d.var()
-                )
+        }
+    }
+
+    // the part of the plot that lies in the calculator's region, drawn over the distribution
+    fn highlighted_plot_data(&mut self) -> Option<PlotData> {
+        let d = self.distribution().ok()?;
+        let (a, b, inclusion) = self.calc_region(d.as_ref());
+        match self.get_plot_data() {
+            PlotData::Bars(bars) => Some(PlotData::Bars(
+                bars.iter()
+                    .filter(|bar| inclusion.vsebuje(a, b, bar.argument))
+                    .cloned()
+                    .collect(),
+            )),
+            PlotData::Line(points) => {
+                let (x_min, x_max) = (points[0][0], points[points.len() - 1][0]);
+                let (lo, hi) = (a.max(x_min), b.min(x_max));
+                if lo >= hi {
+                    return None;
+                }
+                let mut area = vec![[lo, d.gostota(lo)]];
+                area.extend(points.iter().filter(|p| p[0] > lo && p[0] < hi));
+                area.push([hi, d.gostota(hi)]);
+                Some(PlotData::Line(area))
             }
-            DistChoice::EnakomernaInterval => {
-                let d = EnakomernaInterval::new(self.a, self.b);
-                format!(
-                    "Enakomerna (a={:.3}, b={:.3})\nPričakovana vrednost: {:.3}\nVarianca: {:.3}",
-                    self.a, self.b, d.e(), d.var()
-                )
+        }
+    }
+
+    // both plots share the x axis and show pmf/pdf and cdf at the cursor
+    fn plot(&self, id: &str, aspect: f32) -> Plot<'static> {
+        let d = self.distribution().ok();
+        Plot::new(id)
+            .view_aspect(aspect)
+            .legend(Legend::default())
+            .link_axis("distribution", [true, false])
+            .link_cursor("distribution", [true, false])
+            .label_formatter(move |_, point| match &d {
+                Some(d) => hover_label(d.as_ref(), point.x),
+                None => String::new(),
+            })
+    }
+
+    fn density_plot(&mut self, ui: &mut egui::Ui, aspect: f32) {
+        let highlighted = self.highlighted_plot_data();
+        let sample_bars = self.sample_bars();
+        let name = self
+            .distribution()
+            .map(|d| d.to_string())
+            .unwrap_or_default();
+
+        self.plot("distribution-plot", aspect).show(ui, |plot_ui| {
+            match self.get_plot_data() {
+                PlotData::Bars(bars) => {
+                    if !bars.is_empty() {
+                        let chart = BarChart::new(name, bars.clone());
+                        plot_ui.bar_chart(chart);
+                    }
+                }
+                PlotData::Line(points) => {
+                    let line = Line::new(name, PlotPoints::from(points.clone()));
+                    plot_ui.line(line);
+                }
             }
-            DistChoice::Normalna => {
-                let d = Normalna::new(self.mu, self.sigma2);
-                format!(
-                    "Normalna (μ={:.3}, σ²={:.3})\nPričakovana vrednost: {:.3}\nVarianca: {:.3}",
-                    self.mu, self.sigma2, d.e(), d.var()
-                )
+
+            match highlighted {
+                Some(PlotData::Bars(bars)) if !bars.is_empty() => {
+                    plot_ui.bar_chart(BarChart::new("P".to_string(), bars).color(HIGHLIGHT));
+                }
+                Some(PlotData::Line(points)) => {
+                    let area = Line::new("P".to_string(), PlotPoints::from(points))
+                        .color(HIGHLIGHT)
+                        .fill(0.0)
+                        .fill_alpha(0.4);
+                    plot_ui.line(area);
+                }
+                _ => {}
             }
-            DistChoice::Poisson => {
-                let d = Poisson::new(self.lambda);
-                format!(
-                    "Poissonova (λ={:.3})\nPričakovana vrednost: {:.3}\nVarianca: {:.3}",
-                    self.lambda, d.e(), d.var()
-                )
+
+            if let Some(bars) = sample_bars {
+                plot_ui.bar_chart(
+                    BarChart::new("vzorec", bars)
+                        .color(egui::Color32::from_rgba_unmultiplied(120, 120, 120, 140)),
+                );
             }
-            DistChoice::Geometrijska => {
-                let d = Geometrijska::new(self.p);
-                format!(
-                    "Geometrijska (p={:.3})\nPričakovana vrednost: {:.3}\nVarianca: {:.3}",
-                    self.p, d.e(), d.var()
+
+            // discrete layers as points, so they don't hide the bars of the current distribution
+            for layer in self.layers.iter().filter(|l| l.visible) {
+                match &layer.plot_data {
+                    PlotData::Bars(bars) => {
+                        let points: Vec<[f64; 2]> =
+                            bars.iter().map(|bar| [bar.argument, bar.value]).collect();
+                        plot_ui.points(
+                            Points::new(layer.name.clone(), points)
+                                .radius(3.0)
+                                .color(layer.color),
+                        );
+                    }
+                    PlotData::Line(points) => {
+                        plot_ui.line(
+                            Line::new(layer.name.clone(), PlotPoints::from(points.clone()))
+                                .color(layer.color),
+                        );
+                    }
+                }
+            }
+
+            if self.kde_show {
+                let points = self.get_kde_data().clone();
+                plot_ui.line(Line::new("KDE".to_string(), PlotPoints::from(points)));
+            }
+        });
+    }
+
+    fn approximation_view(&mut self, ui: &mut egui::Ui) {
+        let exact = self
+            .distribution()
+            .map(|d| d.to_string())
+            .unwrap_or_default();
+        let bars = match self.get_plot_data() {
+            PlotData::Bars(bars) => bars.clone(),
+            PlotData::Line(_) => vec![],
+        };
+        let Some(data) = self.get_approx_data() else {
+            ui.label(
+                "Približki so na voljo za binomsko, Poissonovo in hipergeometrijsko porazdelitev.",
+            );
+            return;
+        };
+
+        ui.columns(2, |columns| {
+            Plot::new("approximation-plot")
+                .view_aspect(1.2)
+                .legend(Legend::default())
+                .show(&mut columns[0], |plot_ui| {
+                    plot_ui.bar_chart(BarChart::new(exact, bars));
+                    for (approximation, color) in data.approximations.iter().zip(LAYER_COLORS) {
+                        plot_ui.points(
+                            Points::new(approximation.name, approximation.points.clone())
+                                .radius(3.0)
+                                .color(color),
+                        );
+                    }
+                    if !data.normal_pdf.is_empty() {
+                        plot_ui.line(Line::new(
+                            "gostota normalne",
+                            PlotPoints::from(data.normal_pdf.clone()),
+                        ));
+                    }
+                });
+            Plot::new("convergence-plot")
+                .view_aspect(1.2)
+                .legend(Legend::default())
+                .x_axis_label(data.size)
+                .y_axis_label("log10 max |F - G|")
+                .show(&mut columns[1], |plot_ui| {
+                    for ((name, points), color) in data.convergence.iter().zip(LAYER_COLORS) {
+                        plot_ui
+                            .line(Line::new(*name, PlotPoints::from(points.clone())).color(color));
+                    }
+                });
+        });
+
+        egui::Grid::new("approximation_errors")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("približek");
+                ui.label("max |F - G|");
+                ui.label("totalna variacija");
+                ui.end_row();
+                for approximation in &data.approximations {
+                    ui.label(approximation.name);
+                    ui.label(format!("{:.6}", approximation.error.cdf));
+                    ui.label(format!("{:.6}", approximation.error.totalna_variacija));
+                    ui.end_row();
+                }
+            });
+    }
+
+    // draws the next batch of sample means; returns false when there is nothing left to draw
+    fn clt_step(&mut self) -> bool {
+        let Ok(d) = self.distribution() else {
+            return false;
+        };
+
+        let count = self
+            .clt_speed
+            .min(self.clt_repetitions.saturating_sub(self.clt_means.len()));
+        for _ in 0..count {
+            let sample = d.vzorci(&mut self.clt_generator, self.clt_n);
+            self.clt_means
+                .push(sample.iter().sum::<f64>() / self.clt_n as f64);
+        }
+        count > 0
+    }
+
+    fn clt_view(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(egui::Slider::new(&mut self.clt_n, 1..=200).text("velikost vzorca n"));
+            ui.add(
+                egui::Slider::new(&mut self.clt_repetitions, 100..=20000)
+                    .logarithmic(true)
+                    .text("ponovitve"),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.clt_speed, 1..=500)
+                    .logarithmic(true)
+                    .text("povprečij na sliko"),
+            );
+        });
+        ui.horizontal(|ui| {
+            let label = if self.clt_running { "Ustavi" } else { "Začni" };
+            if ui.button(label).clicked() {
+                // starting after a finished run begins a new one
+                if !self.clt_running && self.clt_means.len() >= self.clt_repetitions {
+                    self.clt_means.clear();
+                }
+                self.clt_running = !self.clt_running;
+            }
+            if ui.button("Ponastavi").clicked() {
+                self.clt_means.clear();
+            }
+        });
+
+        // old means are meaningless for another distribution or n
+        let params = (self.current_params(), self.clt_n);
+        if self.clt_params.as_ref() != Some(&params) {
+            self.clt_means.clear();
+            self.clt_params = Some(params);
+        }
+
+        if self.clt_running {
+            if self.clt_step() {
+                ui.ctx().request_repaint();
+            } else {
+                self.clt_running = false;
+            }
+        }
+
+        let Ok(d) = self.distribution() else {
+            return;
+        };
+        let mu = d.e();
+        let sigma2 = d.var() / self.clt_n as f64;
+        // e.g. Student's t with n <= 2
+        if !mu.is_finite() || !sigma2.is_finite() {
+            ui.label("Izrek zahteva končno pričakovano vrednost in varianco.");
+            return;
+        }
+        // the limit is a point mass when the variance is 0
+        let half_width = if sigma2 > 0.0 {
+            4.0 * sigma2.sqrt()
+        } else {
+            1.0
+        };
+        let (lo, hi) = (mu - half_width, mu + half_width);
+
+        let means = &self.clt_means;
+        let mean = means.iter().sum::<f64>() / means.len().max(1) as f64;
+        let variance = means.iter().map(|x| (x - mean).powi(2)).sum::<f64>()
+            / means.len().max(2).saturating_sub(1) as f64;
+        ui.label(format!(
+            "povprečij: {} / {}, njihovo povprečje {:.4} (μ = {:.4}), varianca {:.4} (σ²/n = {:.4})",
+            means.len(),
+            self.clt_repetitions,
+            mean,
+            mu,
+            variance,
+            sigma2
+        ));
+
+        let bars = histogram(means, lo, hi, 50);
+        Plot::new("clt-plot")
+            .view_aspect(2.0)
+            .legend(Legend::default())
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(BarChart::new("povprečja vzorcev", bars));
+                if sigma2 > 0.0 {
+                    let normal = Normalna::new(mu, sigma2);
+                    let points: Vec<[f64; 2]> = (0..=500)
+                        .map(|i| {
+                            let x = lo + (hi - lo) * i as f64 / 500.0;
+                            [x, normal.pdf(x)]
+                        })
+                        .collect();
+                    plot_ui.line(
+                        Line::new(format!("N(μ, σ²/{})", self.clt_n), PlotPoints::from(points))
+                            .color(HIGHLIGHT),
+                    );
+                }
+            });
+    }
+
+    fn simulate(&mut self) {
+        let Ok(d) = self.distribution() else {
+            return;
+        };
+        self.sim_seed += 1;
+        let sample = d.vzorci(&mut Generator::new(self.sim_seed), self.sim_n);
+        let name = if d.diskretna() { "χ²" } else { "KS" };
+        self.sim_test = Some((name, test_prileganja(&sample, d.as_ref(), 0)));
+        self.sim_sample = Some(sample);
+        self.cached_probability = None;
+    }
+
+    fn simulation_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("Simuliraj").clicked() {
+                self.simulate();
+            }
+            ui.add(
+                egui::Slider::new(&mut self.sim_n, 10..=100000)
+                    .logarithmic(true)
+                    .text("velikost vzorca"),
+            );
+            ui.add(egui::Slider::new(&mut self.sim_bins, 5..=100).text("razredi"));
+            if self.sim_sample.is_some() && ui.button("Počisti").clicked() {
+                self.sim_sample = None;
+                self.cached_probability = None;
+            }
+        });
+
+        let (Some(sample), Some((name, test)), Ok(d)) =
+            (&self.sim_sample, &self.sim_test, self.distribution())
+        else {
+            return;
+        };
+        let n = sample.len() as f64;
+        let mean = sample.iter().sum::<f64>() / n;
+        let variance = sample.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        ui.label(format!(
+            "povprečje vzorca {:.4} (E = {:.4}), varianca vzorca {:.4} (Var = {:.4}), {} = {:.4}, p = {:.4}",
+            mean,
+            d.e(),
+            variance,
+            d.var(),
+            name,
+            test.statistika,
+            test.p_vrednost
+        ));
+    }
+
+    // relative frequencies for discrete distributions, a density histogram otherwise
+    fn sample_bars(&mut self) -> Option<Vec<Bar>> {
+        // refreshes the caches if the parameters changed
+        self.get_plot_data();
+        let sample = self.sim_sample.as_ref()?;
+        match self.cached_plot_data.as_ref().unwrap() {
+            PlotData::Bars(bars) if !bars.is_empty() => {
+                let first = bars[0].argument;
+                let mut counts = vec![0usize; bars.len()];
+                for &x in sample {
+                    let i = x - first;
+                    if i >= 0.0 && i < counts.len() as f64 {
+                        counts[i as usize] += 1;
+                    }
+                }
+                let n = sample.len() as f64;
+                Some(
+                    bars.iter()
+                        .zip(counts)
+                        .map(|(bar, count)| Bar::new(bar.argument, count as f64 / n).width(0.3))
+                        .collect(),
                 )
             }
-            DistChoice::Hipergeometrijska => {
-                let d = Hipergeometrijska::new(self.s, self.r, self.n);
+            PlotData::Bars(_) => None,
+            PlotData::Line(points) => Some(histogram(
+                sample,
+                points[0][0],
+                points[points.len() - 1][0],
+                self.sim_bins,
+            )),
+        }
+    }
+
+    fn load_data(&mut self, text: Result<String, String>) {
+        match text.and_then(|text| parse_csv(&text)) {
+            Ok(columns) => {
+                self.data_columns = columns;
+                self.data_column = 0;
+                self.data_bars = None;
+                self.data_error = None;
+            }
+            Err(err) => self.data_error = Some(err),
+        }
+        self.reset_data_fits();
+        self.cached_probability = None;
+    }
+
+    // dropping the receiver discards the result of a fit that is still running
+    fn reset_data_fits(&mut self) {
+        self.data_fits = None;
+        self.data_fitting = None;
+    }
+
+    // starts fitting the chosen column in the background and picks up the fits once they are ready
+    fn update_data_fits(&mut self, ctx: &egui::Context) {
+        if self.data_fits.is_none() && self.data_fitting.is_none() {
+            let data = self.data_columns[self.data_column].1.clone();
+            let criterion = self.data_criterion;
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                if sender.send(najboljse_prileganje(&data, criterion)).is_ok() {
+                    ctx.request_repaint();
+                }
+            });
+            self.data_fitting = Some(receiver);
+        }
+        if let Some(Ok(mut fits)) = self
+            .data_fitting
+            .as_ref()
+            .map(|receiver| receiver.try_recv())
+        {
+            // the criterion may have changed while fitting
+            sort_fits(&mut fits, self.data_criterion);
+            self.data_fits = Some(fits);
+            self.data_fitting = None;
+        }
+    }
+
+    fn data_view(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Datoteka CSV:");
+            ui.text_edit_singleline(&mut self.data_path);
+            if ui.button("Naloži").clicked() {
+                let text = std::fs::read_to_string(&self.data_path).map_err(|err| err.to_string());
+                self.load_data(text);
+            }
+            ui.label("ali jo povlecite v okno");
+        });
+        if let Some(err) = &self.data_error {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+        }
+        if self.data_columns.is_empty() {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            let old = self.data_column;
+            egui::ComboBox::from_label("stolpec")
+                .selected_text(&self.data_columns[self.data_column].0)
+                .show_ui(ui, |ui| {
+                    for (i, (name, values)) in self.data_columns.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut self.data_column,
+                            i,
+                            format!("{} ({} vrednosti)", name, values.len()),
+                        );
+                    }
+                });
+            if old != self.data_column {
+                self.reset_data_fits();
+                self.data_bars = None;
+                self.cached_probability = None;
+            }
+            if ui
+                .add(egui::Slider::new(&mut self.data_bins, 5..=100).text("razredi"))
+                .changed()
+            {
+                self.data_bars = None;
+            }
+            ui.label("razvrsti po:");
+            for (criterion, label) in [
+                (Kriterij::Aic, "AIC"),
+                (Kriterij::Bic, "BIC"),
+                (Kriterij::Ks, "KS"),
+            ] {
+                if ui
+                    .selectable_value(&mut self.data_criterion, criterion, label)
+                    .changed()
+                    && let Some(fits) = &mut self.data_fits
+                {
+                    sort_fits(fits, criterion);
+                }
+            }
+        });
+
+        self.update_data_fits(ui.ctx());
+        let data = &self.data_columns[self.data_column].1;
+        let fits = self.data_fits.as_deref().unwrap_or_default();
+        let (lo, hi) = data_range(data);
+        let bars = self
+            .data_bars
+            .get_or_insert_with(|| data_bars(data, self.data_bins))
+            .clone();
+
+        Plot::new("data-plot")
+            .view_aspect(2.0)
+            .legend(Legend::default())
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(BarChart::new("podatki", bars).color(egui::Color32::GRAY));
+
+                for (fit, color) in fits.iter().zip(LAYER_COLORS.iter().cycle()) {
+                    let d = fit.porazdelitev.as_ref();
+                    let name = d.to_string();
+                    if d.diskretna() {
+                        // cap the number of points like plot_data does
+                        let points: Vec<[f64; 2]> = (lo as u64..=hi.min(lo + 1000.0) as u64)
+                            .map(|k| [k as f64, d.gostota(k as f64)])
+                            .collect();
+                        plot_ui.points(Points::new(name, points).radius(3.0).color(*color));
+                    } else {
+                        let points: Vec<[f64; 2]> = (0..=500)
+                            .map(|i| {
+                                let x = lo + (hi - lo) * i as f64 / 500.0;
+                                [x, d.gostota(x)]
+                            })
+                            .collect();
+                        plot_ui.line(Line::new(name, PlotPoints::from(points)).color(*color));
+                    }
+                }
+            });
+
+        if self.data_fitting.is_some() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("prilagajam porazdelitve ...");
+            });
+            return;
+        }
+        egui::Grid::new("data_fits").striped(true).show(ui, |ui| {
+            for header in ["porazdelitev", "ln L", "AIC", "BIC", "KS", "test", "p"] {
+                ui.label(header);
+            }
+            ui.end_row();
+            for fit in fits {
+                let discrete = fit.porazdelitev.diskretna();
+                ui.label(fit.porazdelitev.to_string());
+                ui.label(format!("{:.2}", fit.log_verjetje));
+                ui.label(format!("{:.2}", fit.aic));
+                ui.label(format!("{:.2}", fit.bic));
+                ui.label(format!("{:.4}", fit.ks));
+                ui.label(format!(
+                    "{} = {:.4}",
+                    if discrete { "χ²" } else { "KS" },
+                    fit.test.statistika
+                ));
+                ui.label(format!("{:.4}", fit.test.p_vrednost));
+                ui.end_row();
+            }
+        });
+    }
+
+    fn get_probability_data(&mut self) -> Option<&ProbabilityData> {
+        // refreshes the caches if the parameters changed
+        self.get_plot_data();
+        if self.cached_probability.is_none() {
+            let sample = if self.prob_use_data {
+                self.data_columns
+                    .get(self.data_column)
+                    .map(|(_, values)| values)
+            } else {
+                self.sim_sample.as_ref()
+            };
+            let data = match (sample, self.distribution()) {
+                (Some(sample), Ok(d)) if !sample.is_empty() => Some(probability_data(
+                    sample,
+                    d.as_ref(),
+                    self.prob_positions,
+                    self.prob_alpha,
+                )),
+                _ => None,
+            };
+            self.cached_probability = Some(data);
+        }
+
+        self.cached_probability.as_ref().unwrap().as_ref()
+    }
+
+    fn probability_view(&mut self, ui: &mut egui::Ui) {
+        let old = (self.prob_use_data, self.prob_positions, self.prob_alpha);
+        ui.horizontal(|ui| {
+            ui.label("vzorec:");
+            ui.radio_value(&mut self.prob_use_data, false, "simuliran");
+            ui.add_enabled_ui(!self.data_columns.is_empty(), |ui| {
+                ui.radio_value(&mut self.prob_use_data, true, "podatki");
+            });
+            egui::ComboBox::from_label("položaji")
+                .selected_text(format!("{:?}", self.prob_positions))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(
+                        &mut self.prob_positions,
+                        Polozaji::Blom,
+                        "Blom, (i - 3/8) / (n + 1/4)",
+                    );
+                    ui.selectable_value(
+                        &mut self.prob_positions,
+                        Polozaji::Hazen,
+                        "Hazen, (i - 1/2) / n",
+                    );
+                    ui.selectable_value(
+                        &mut self.prob_positions,
+                        Polozaji::Weibull,
+                        "Weibull, i / (n + 1)",
+                    );
+                });
+            ui.add(
+                egui::Slider::new(&mut self.prob_alpha, 0.001..=0.2)
+                    .logarithmic(true)
+                    .text("α pasu zaupanja"),
+            );
+        });
+        if old != (self.prob_use_data, self.prob_positions, self.prob_alpha) {
+            self.cached_probability = None;
+        }
+        if !self.prob_use_data {
+            self.simulation_ui(ui);
+        }
+
+        let Some(data) = self.get_probability_data() else {
+            ui.label("Za prikaz simulirajte vzorec ali naložite podatke.");
+            return;
+        };
+        let band_color = egui::Color32::GRAY;
+        ui.columns(2, |columns| {
+            let (lo, hi) = data
+                .qq
+                .iter()
+                .flatten()
+                .filter(|x| x.is_finite())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| {
+                    (lo.min(x), hi.max(x))
+                });
+            Plot::new("qq-plot")
+                .view_aspect(1.0)
+                .legend(Legend::default())
+                .x_axis_label("teoretični kvantili")
+                .y_axis_label("vzorec")
+                .show(&mut columns[0], |plot_ui| {
+                    plot_ui.line(
+                        Line::new("y = x", PlotPoints::from(vec![[lo, lo], [hi, hi]]))
+                            .color(HIGHLIGHT),
+                    );
+                    plot_ui.line(
+                        Line::new("pas zaupanja", PlotPoints::from(data.qq_band.0.clone()))
+                            .color(band_color),
+                    );
+                    plot_ui.line(
+                        Line::new("pas zaupanja", PlotPoints::from(data.qq_band.1.clone()))
+                            .color(band_color),
+                    );
+                    plot_ui.points(Points::new("Q-Q", data.qq.clone()).radius(2.0));
+                });
+
+            let eps = data.pp_band;
+            let band = |shift: f64| -> Vec<[f64; 2]> {
+                (0..=100)
+                    .map(|i| i as f64 / 100.0)
+                    .map(|x| [x, (x + shift).clamp(0.0, 1.0)])
+                    .collect()
+            };
+            Plot::new("pp-plot")
+                .view_aspect(1.0)
+                .legend(Legend::default())
+                .x_axis_label("položaji p_i")
+                .y_axis_label("F(x_(i))")
+                .show(&mut columns[1], |plot_ui| {
+                    plot_ui.line(
+                        Line::new("y = x", PlotPoints::from(vec![[0.0, 0.0], [1.0, 1.0]]))
+                            .color(HIGHLIGHT),
+                    );
+                    plot_ui.line(
+                        Line::new("pas zaupanja", PlotPoints::from(band(-eps))).color(band_color),
+                    );
+                    plot_ui.line(
+                        Line::new("pas zaupanja", PlotPoints::from(band(eps))).color(band_color),
+                    );
+                    plot_ui.points(Points::new("P-P", data.pp.clone()).radius(2.0));
+                });
+        });
+    }
+
+    fn cdf_plot(&mut self, ui: &mut egui::Ui, aspect: f32) {
+        self.plot("cdf-plot", aspect).show(ui, |plot_ui| {
+            let name = self
+                .distribution()
+                .map(|d| d.to_string())
+                .unwrap_or_default();
+            let points = self.get_cdf_data().clone();
+            plot_ui.line(Line::new(name, PlotPoints::from(points)));
+
+            for layer in self.layers.iter().filter(|l| l.visible) {
+                plot_ui.line(
+                    Line::new(layer.name.clone(), PlotPoints::from(layer.cdf.clone()))
+                        .color(layer.color),
+                );
+            }
+        });
+    }
+
+    fn update_info(&mut self) {
+        let entry = self.entry();
+        let params = &self.params[self.selected];
+
+        self.info = match self.distribution() {
+            Ok(d) => {
+                let params: Vec<String> = entry
+                    .parametri
+                    .iter()
+                    .zip(params)
+                    .map(|(p, x)| {
+                        if p.celo_stevilo {
+                            format!("{}={}", p.ime, x)
+                        } else {
+                            format!("{}={:.3}", p.ime, x)
+                        }
+                    })
+                    .collect();
                 format!(
-                    "Hipergeometrijska (s={}, r={}, n={})\nPričakovana vrednost: {:.3}\nVarianca: {:.3}",
-                    self.s, self.r, self.n, d.e(), d.var()
+                    "{} ({})\nPričakovana vrednost: {:.3}\nVarianca: {:.3}",
+                    entry.prikazno_ime,
+                    params.join(", "),
+                    d.e(),
+                    d.var()
                 )
             }
+            Err(err) => format!("Neveljavni parametri: {}", err),
         };
+        if let Ok(d) = self.distribution() {
+            self.spec = d.to_string();
+        }
+    }
+
+    fn saved_state(&self) -> SavedState {
+        SavedState {
+            selected: self.entry().ime.to_string(),
+            params: register::REGISTER
+                .iter()
+                .map(|v| v.ime.to_string())
+                .zip(self.params.clone())
+                .collect(),
+            theme: self.theme,
+        }
+    }
+
+    // values of distributions that are no longer in the register, or changed their parameters, are dropped
+    fn restore(&mut self, state: SavedState) {
+        let position = |name: &str| register::REGISTER.iter().position(|v| v.ime == name);
+        if let Some(i) = position(&state.selected) {
+            self.selected = i;
+        }
+        for (name, values) in state.params {
+            if let Some(i) = position(&name)
+                && values.len() == register::REGISTER[i].parametri.len()
+            {
+                self.params[i] = values;
+            }
+        }
+        self.theme = state.theme;
+    }
+
+    fn load_presets(&mut self) {
+        let Some(path) = presets_path() else {
+            return;
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                let (presets, errors) = parse_presets(&text);
+                self.presets = presets;
+                self.preset_error = (!errors.is_empty()).then(|| {
+                    format!(
+                        "{}: {}\nneveljavne vrstice bodo ob shranjevanju izpuščene",
+                        path.display(),
+                        errors.join("; ")
+                    )
+                });
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => self.preset_error = Some(format!("{}: {}", path.display(), err)),
+        }
+    }
+
+    fn save_presets(&mut self) {
+        let Some(path) = presets_path() else {
+            self.preset_error = Some("ni mape za shranjevanje nastavitev".to_string());
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, format_presets(&self.presets)));
+        self.preset_error = result
+            .err()
+            .map(|err| format!("{}: {}", path.display(), err));
+    }
+
+    // returns true if a preset was loaded
+    fn presets_ui(&mut self, ui: &mut egui::Ui) -> bool {
+        ui.horizontal(|ui| {
+            ui.label("Ime:");
+            ui.text_edit_singleline(&mut self.preset_name);
+            let name = self.preset_name.trim().to_string();
+            let error = preset_name_error(&name);
+            if let Some(err) = error.filter(|_| !name.is_empty()) {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
+            if ui
+                .add_enabled(error.is_none(), egui::Button::new("Shrani trenutno"))
+                .clicked()
+                && let Ok(d) = self.distribution()
+            {
+                let spec = d.to_string();
+                match self.presets.iter_mut().find(|p| p.name == name) {
+                    Some(preset) => preset.spec = spec,
+                    None => self.presets.push(Preset { name, spec }),
+                }
+                self.save_presets();
+            }
+        });
+
+        let (mut load, mut delete) = (None, None);
+        for (i, preset) in self.presets.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.button("Naloži").clicked() {
+                    load = Some(i);
+                }
+                if ui.button("Izbriši").clicked() {
+                    delete = Some(i);
+                }
+                ui.label(format!("{}: {}", preset.name, preset.spec));
+            });
+        }
+        if let Some(path) = presets_path() {
+            ui.weak(format!("datoteka: {}", path.display()));
+        }
+        if let Some(err) = &self.preset_error {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+        }
+
+        if let Some(i) = delete {
+            self.presets.remove(i);
+            self.save_presets();
+        }
+        if let Some(i) = load {
+            self.spec = self.presets[i].spec.clone();
+            self.apply_spec();
+            return true;
+        }
+        false
+    }
+
+    fn apply_spec(&mut self) {
+        match razcleni(&self.spec) {
+            Ok(d) => {
+                self.selected = register::REGISTER
+                    .iter()
+                    .position(|v| v.ime == d.ime())
+                    .unwrap();
+                self.params[self.selected] = d.parametri();
+                self.spec_error = None;
+            }
+            Err(err) => self.spec_error = Some(err.oznaci(&self.spec)),
+        }
     }
 }
 
 impl eframe::App for MyApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, &self.saved_state());
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        
+        self.theme = ctx.options(|o| o.theme_preference);
+        for file in ctx.input(|i| i.raw.dropped_files.clone()) {
+            let text = match (&file.path, &file.bytes) {
+                (Some(path), _) => {
+                    self.data_path = path.display().to_string();
+                    std::fs::read_to_string(path).map_err(|err| err.to_string())
+                }
+                (None, Some(bytes)) => {
+                    String::from_utf8(bytes.to_vec()).map_err(|err| err.to_string())
+                }
+                (None, None) => continue,
+            };
+            self.load_data(text);
+            self.view = View::Data;
+        }
+
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             // The top panel is often a good place for a menu bar:
 
@@ -282,100 +1418,57 @@ impl eframe::App for MyApp {
                 egui::widgets::global_theme_preference_buttons(ui);
             });
         });
-        
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("Raziskovalec porazdelitev");
 
+            let old_selected = self.selected;
             egui::ComboBox::from_label("Izberi porazdelitev")
-                .selected_text(format!("{:?}", self.selected))
+                .selected_text(self.entry().prikazno_ime)
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.selected, DistChoice::Bernoulli, "Bernoullijeva");
-                    ui.selectable_value(&mut self.selected, DistChoice::Binomska, "Binomska");
-                    ui.selectable_value(&mut self.selected, DistChoice::Eksponentna, "Eksponentna");
-                    ui.selectable_value(&mut self.selected, DistChoice::EnakomernaInterval, "Enakomerna na intervalu");
-                    ui.selectable_value(&mut self.selected, DistChoice::Geometrijska, "Geometrijska");
-                    ui.selectable_value(&mut self.selected, DistChoice::Hipergeometrijska, "Hipergeometrijska");
-                    ui.selectable_value(&mut self.selected, DistChoice::Normalna, "Normalna");
-                    ui.selectable_value(&mut self.selected, DistChoice::Poisson, "Poissonova");
+                    for (i, entry) in register::REGISTER.iter().enumerate() {
+                        ui.selectable_value(&mut self.selected, i, entry.prikazno_ime);
+                    }
                 });
 
             ui.separator();
 
-            let mut params_changed = false;
+            let mut params_changed = old_selected != self.selected;
 
-            match self.selected {
-                DistChoice::Bernoulli | DistChoice::Geometrijska => {
-                    let old_p = self.p;
-                    ui.add(egui::Slider::new(&mut self.p, 0.01..=0.99).text("p"));
-                    if (old_p - self.p).abs() > f64::EPSILON {
-                        params_changed = true;
-                    }
-                }
-                DistChoice::Binomska => {
-                    let old_n = self.n;
-                    let old_p = self.p;
-                    ui.add(egui::Slider::new(&mut self.n, 1..=100).text("n"));
-                    ui.add(egui::Slider::new(&mut self.p, 0.01..=0.99).text("p"));
-                    if old_n != self.n || (old_p - self.p).abs() > f64::EPSILON {
-                        params_changed = true;
-                    }
-                }
-                DistChoice::Eksponentna => {
-                    let old_lambda = self.lambda;
-                    ui.add(egui::Slider::new(&mut self.lambda, 0.1..=10.0).text("λ"));
-                    if (old_lambda - self.lambda).abs() > f64::EPSILON {
-                        params_changed = true;
-                    }
-                }
-                DistChoice::EnakomernaInterval => {
-                    let old_a = self.a;
-                    let old_b = self.b;
-                    ui.add(egui::Slider::new(&mut self.a, -10.0..=10.0).text("a"));
-                    ui.add(egui::Slider::new(&mut self.b, -10.0..=10.0).text("b"));
-
-                    // Ensure a < b
-                    if self.a >= self.b {
-                        self.b = self.a + 0.1;
-                    }
-                    
-                    if (old_a - self.a).abs() > f64::EPSILON || (old_b - self.b).abs() > f64::EPSILON {
-                        params_changed = true;
-                    }
+            ui.horizontal(|ui| {
+                ui.label("Zapis:");
+                let response = ui.text_edit_singleline(&mut self.spec);
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    self.apply_spec();
+                    params_changed = true;
                 }
-                DistChoice::Normalna => {
-                    let old_mu = self.mu;
-                    let old_sigma2 = self.sigma2;
-                    ui.add(egui::Slider::new(&mut self.mu, -10.0..=10.0).text("μ"));
-                    ui.add(egui::Slider::new(&mut self.sigma2, 0.1..=5.0).text("σ²"));
-                    if (old_mu - self.mu).abs() > f64::EPSILON || (old_sigma2 - self.sigma2).abs() > f64::EPSILON {
-                        params_changed = true;
-                    }
+            });
+            if let Some(err) = &self.spec_error {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    egui::RichText::new(err).monospace(),
+                );
+            }
+            ui.collapsing("Prednastavitve", |ui| {
+                if self.presets_ui(ui) {
+                    params_changed = true;
                 }
-                DistChoice::Poisson => {
-                    let old_lambda = self.lambda;
-                    ui.add(egui::Slider::new(&mut self.lambda, 0.1..=20.0).text("λ"));
-                    if (old_lambda - self.lambda).abs() > f64::EPSILON {
-                        params_changed = true;
-                    }
+            });
+
+            let entry = self.entry();
+            for (param, value) in entry.parametri.iter().zip(&mut self.params[self.selected]) {
+                let old = *value;
+                let (lo, hi) = param.razpon;
+                let label = format!("{} ({})", param.ime, param.opis);
+                if param.celo_stevilo {
+                    let mut k = *value as u64;
+                    ui.add(egui::Slider::new(&mut k, lo as u64..=hi as u64).text(label));
+                    *value = k as f64;
+                } else {
+                    ui.add(egui::Slider::new(value, lo..=hi).text(label));
                 }
-                DistChoice::Hipergeometrijska => {
-                    let old_s = self.s;
-                    let old_r = self.r;
-                    let old_n = self.n;
-                    ui.add(egui::Slider::new(&mut self.s, 1..=100).text("s (uspešni elementi)"));
-                    ui.add(egui::Slider::new(&mut self.r, 1..=100).text("r (skupaj elementov)"));
-                    ui.add(egui::Slider::new(&mut self.n, 1..=100).text("n (vzorec)"));
-                    
-                    if self.s > self.r {
-                        self.r = self.s;
-                    }
-                    if self.n > self.r {
-                        self.n = self.r;
-                    }
-                    
-                    if old_s != self.s || old_r != self.r || old_n != self.n {
-                        params_changed = true;
-                    }
+                if (old - *value).abs() > f64::EPSILON {
+                    params_changed = true;
                 }
             }
 
@@ -383,39 +1476,720 @@ impl eframe::App for MyApp {
                 self.update_info();
                 self.cached_params = None;
                 self.cached_plot_data = None;
+                self.cached_cdf = None;
             }
 
             ui.separator();
             ui.label(&self.info);
 
-            let plot = Plot::new("distribution-plot")
-                                                    .view_aspect(2.0);
-
-            plot.show(ui, |plot_ui| {
-                match self.get_plot_data() {
-                    PlotData::Bars(bars) => {
-                        if !bars.is_empty() {
-                            let chart = BarChart::new("Distribution".to_string(), bars.clone());
-                            plot_ui.bar_chart(chart);
-                        }
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Kalkulator:");
+                ui.radio_value(
+                    &mut self.calc_mode,
+                    CalcMode::Interval,
+                    "verjetnost intervala",
+                );
+                ui.radio_value(&mut self.calc_mode, CalcMode::Quantile, "kvantil");
+            });
+            ui.horizontal(|ui| {
+                match self.calc_mode {
+                    CalcMode::Interval => {
+                        ui.label("a");
+                        ui.add(
+                            egui::DragValue::new(&mut self.calc_a)
+                                .speed(0.1)
+                                .range(CALC_RANGE),
+                        );
+                        ui.label("b");
+                        ui.add(
+                            egui::DragValue::new(&mut self.calc_b)
+                                .speed(0.1)
+                                .range(CALC_RANGE),
+                        );
+                        egui::ComboBox::from_id_salt("calc_inclusion")
+                            .selected_text(
+                                INCLUSIONS
+                                    .iter()
+                                    .find(|(v, _)| *v == self.calc_inclusion)
+                                    .unwrap()
+                                    .1,
+                            )
+                            .show_ui(ui, |ui| {
+                                for (v, label) in INCLUSIONS {
+                                    ui.selectable_value(&mut self.calc_inclusion, v, label);
+                                }
+                            });
                     }
-                    PlotData::Line(points) => {
-                        let line = Line::new("Distribution".to_string(), PlotPoints::from(points.clone()));
-                        plot_ui.line(line);
+                    CalcMode::Quantile => {
+                        ui.label("p");
+                        ui.add(
+                            egui::DragValue::new(&mut self.calc_p)
+                                .speed(0.001)
+                                .range(0.0..=1.0),
+                        );
                     }
                 }
+                ui.label(self.calc_result());
+            });
+
+            ui.horizontal(|ui| {
+                let old = (self.kde_show, self.kde_n, self.kde_jedro, self.kde_sirina);
+                ui.checkbox(&mut self.kde_show, "Jedrna ocena gostote");
+                if self.kde_show {
+                    ui.add(egui::Slider::new(&mut self.kde_n, 10..=2000).text("velikost vzorca"));
+                    egui::ComboBox::from_id_salt("kde_jedro")
+                        .selected_text(format!("{:?}", self.kde_jedro))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.kde_jedro, Jedro::Gaussovo, "Gaussovo");
+                            ui.selectable_value(
+                                &mut self.kde_jedro,
+                                Jedro::Epanechnikovo,
+                                "Epanechnikovo",
+                            );
+                        });
+                    egui::ComboBox::from_id_salt("kde_sirina")
+                        .selected_text(format!("{:?}", self.kde_sirina))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.kde_sirina,
+                                SirinaPasu::Silverman,
+                                "Silverman",
+                            );
+                            ui.selectable_value(&mut self.kde_sirina, SirinaPasu::Scott, "Scott");
+                            ui.selectable_value(
+                                &mut self.kde_sirina,
+                                SirinaPasu::PrecnoPreverjanje,
+                                "Prečno preverjanje",
+                            );
+                        });
+                    if ui.button("Nov vzorec").clicked() {
+                        self.kde_seed += 1;
+                        self.cached_kde = None;
+                    }
+                }
+                if old != (self.kde_show, self.kde_n, self.kde_jedro, self.kde_sirina) {
+                    self.cached_kde = None;
+                }
             });
+
+            ui.separator();
+            self.layers_ui(ui);
+
+            ui.horizontal(|ui| {
+                ui.label("Prikaz:");
+                ui.radio_value(&mut self.view, View::Density, "gostota");
+                ui.radio_value(&mut self.view, View::Cdf, "porazdelitvena funkcija");
+                ui.radio_value(&mut self.view, View::Both, "oboje");
+                ui.radio_value(&mut self.view, View::Approximation, "približki");
+                ui.radio_value(&mut self.view, View::Clt, "centralni limitni izrek");
+                ui.radio_value(&mut self.view, View::Data, "podatki");
+                ui.radio_value(&mut self.view, View::Probability, "Q-Q in P-P");
+            });
+
+            if matches!(self.view, View::Density | View::Both) {
+                self.simulation_ui(ui);
+            }
+            if matches!(self.view, View::Density | View::Cdf | View::Both) {
+                self.export_ui(ui);
+            }
+
+            match self.view {
+                View::Density => self.density_plot(ui, 2.0),
+                View::Cdf => self.cdf_plot(ui, 2.0),
+                View::Both => {
+                    ui.columns(2, |columns| {
+                        self.density_plot(&mut columns[0], 1.2);
+                        self.cdf_plot(&mut columns[1], 1.2);
+                    });
+                }
+                View::Approximation => self.approximation_view(ui),
+                View::Clt => self.clt_view(ui),
+                View::Data => self.data_view(ui),
+                View::Probability => self.probability_view(ui),
+            }
         });
     }
 }
 
+fn probability_data(
+    sample: &[f64],
+    d: &dyn DynPorazdelitev,
+    positions: Polozaji,
+    alpha: f64,
+) -> ProbabilityData {
+    let qq: Vec<[f64; 2]> = qq_tocke(sample, d, positions)
+        .into_iter()
+        .map(|(x, y)| [x, y])
+        .collect();
+    let pp = pp_tocke(sample, d, positions)
+        .into_iter()
+        .map(|(x, y)| [x, y])
+        .collect();
+    let eps = ks_pas(sample.len(), alpha);
+
+    // the band is drawn over the range of the finite theoretical quantiles
+    let finite: Vec<f64> = qq.iter().map(|p| p[0]).filter(|x| x.is_finite()).collect();
+    let (lo, hi) = match (finite.first(), finite.last()) {
+        (Some(&lo), Some(&hi)) => (lo, hi),
+        _ => (0.0, 0.0),
+    };
+    let x: Vec<f64> = (0..=200)
+        .map(|i| lo + (hi - lo) * i as f64 / 200.0)
+        .collect();
+    let (mut lower, mut upper) = (vec![], vec![]);
+    for (x, spodnja, zgornja) in qq_pas(d, &x, eps) {
+        if spodnja.is_finite() {
+            lower.push([x, spodnja]);
+        }
+        if zgornja.is_finite() {
+            upper.push([x, zgornja]);
+        }
+    }
+
+    ProbabilityData {
+        qq,
+        pp,
+        qq_band: (lower, upper),
+        pp_band: eps,
+    }
+}
+
+fn plot_data(d: &dyn DynPorazdelitev) -> PlotData {
+    if d.diskretna() {
+        // cap the number of bars for very wide supports
+        let x_min = d.kvantil(0.0005);
+        let x_max = d.kvantil(0.9995).min(x_min + 1000.0);
+
+        let bars: Vec<Bar> = (x_min as u64..=x_max as u64)
+            .map(|k| Bar::new(k as f64, d.gostota(k as f64)).width(0.6))
+            .collect();
+
+        PlotData::Bars(bars)
+    } else {
+        let lo = d.kvantil(0.001);
+        let hi = d.kvantil(0.999);
+        let margin = (hi - lo) * 0.1;
+        let x_min = lo - margin;
+        let x_max = hi + margin;
+
+        let xs: Vec<f64> = (0..=500)
+            .map(|i| x_min + (x_max - x_min) * i as f64 / 500.0)
+            .collect();
+        let mut ys = vec![0.0; xs.len()];
+        d.gostota_batch(&xs, &mut ys);
+        let points: Vec<[f64; 2]> = xs.iter().zip(&ys).map(|(&x, &y)| [x, y]).collect();
+
+        PlotData::Line(points)
+    }
+}
+
+// the cdf over the same range as the density plot, a step function for discrete distributions
+fn cdf_points(d: &dyn DynPorazdelitev, plot_data: &PlotData) -> Vec<[f64; 2]> {
+    let xs: Vec<f64> = match plot_data {
+        PlotData::Bars(bars) => bars.iter().map(|bar| bar.argument).collect(),
+        PlotData::Line(points) => points.iter().map(|p| p[0]).collect(),
+    };
+    if xs.is_empty() {
+        return vec![];
+    }
+
+    let mut ys = vec![0.0; xs.len()];
+    d.cdf_batch(&xs, &mut ys);
+    if d.diskretna() {
+        let mut steps = vec![[xs[0] - 1.0, d.cdf(xs[0] - 1.0)]];
+        for (&x, &y) in xs.iter().zip(&ys) {
+            let previous = steps[steps.len() - 1][1];
+            steps.push([x, previous]);
+            steps.push([x, y]);
+        }
+        steps.push([xs[xs.len() - 1] + 1.0, ys[ys.len() - 1]]);
+        steps
+    } else {
+        xs.iter().zip(&ys).map(|(&x, &y)| [x, y]).collect()
+    }
+}
+
+// the normal approximation with and without continuity correction, if the variance is positive
+fn normal_approximations(normal: Option<Normalna>) -> Vec<(&'static str, Box<dyn Diskretna>)> {
+    match normal {
+        Some(normal) => vec![
+            (
+                "normalna",
+                Box::new(Diskretizirana::new(normal.clone(), false)),
+            ),
+            (
+                "normalna s popravkom",
+                Box::new(Diskretizirana::new(normal, true)),
+            ),
+        ],
+        None => vec![],
+    }
+}
+
+fn binomial_approximations(b: &Binomska) -> Vec<(&'static str, Box<dyn Diskretna>)> {
+    let mut approximations: Vec<(&'static str, Box<dyn Diskretna>)> =
+        vec![("Poisson", Box::new(b.poissonov_priblizek()))];
+    approximations.extend(normal_approximations(b.normalni_priblizek()));
+    approximations
+}
+
+fn poisson_approximations(p: &Poisson) -> Vec<(&'static str, Box<dyn Diskretna>)> {
+    normal_approximations(p.normalni_priblizek())
+}
+
+fn hypergeometric_approximations(h: &Hipergeometrijska) -> Vec<(&'static str, Box<dyn Diskretna>)> {
+    let mut approximations: Vec<(&'static str, Box<dyn Diskretna>)> =
+        vec![("binomska", Box::new(h.binomski_priblizek()))];
+    approximations.extend(normal_approximations(h.normalni_priblizek()));
+    approximations
+}
+
+fn approximation_errors<D: Diskretna + Kvantil>(
+    exact: &D,
+    approximations: &[(&'static str, Box<dyn Diskretna>)],
+) -> Vec<(&'static str, NapakaPriblizka)> {
+    approximations
+        .iter()
+        .map(|(name, approximation)| (*name, napaka_priblizka(exact, approximation.as_ref())))
+        .collect()
+}
+
+fn approx_data(d: &dyn DynPorazdelitev) -> Option<ApproxData> {
+    let params = d.parametri();
+    // errors of the approximations for a growing size parameter
+    let (approximations, errors, convergence, size) = match d.ime() {
+        "Binomska" => {
+            let b = Binomska::new(params[0] as u64, params[1]);
+            let approximations = binomial_approximations(&b);
+            let convergence: Vec<_> = (1..=200)
+                .map(|n| {
+                    let b = Binomska::new(n, b.p);
+                    (
+                        n as f64,
+                        approximation_errors(&b, &binomial_approximations(&b)),
+                    )
+                })
+                .collect();
+            let errors = approximation_errors(&b, &approximations);
+            (approximations, errors, convergence, "n")
+        }
+        "Poisson" => {
+            let p = Poisson::new(params[0]);
+            let approximations = poisson_approximations(&p);
+            let convergence: Vec<_> = (1..=200)
+                .map(|i| {
+                    let p = Poisson::new(i as f64 * 0.5);
+                    (
+                        p.lambda,
+                        approximation_errors(&p, &poisson_approximations(&p)),
+                    )
+                })
+                .collect();
+            let errors = approximation_errors(&p, &approximations);
+            (approximations, errors, convergence, "λ")
+        }
+        "Hipergeometrijska" => {
+            let h = Hipergeometrijska::new(params[0] as u64, params[1] as u64, params[2] as u64);
+            let approximations = hypergeometric_approximations(&h);
+            // a population m times larger with the same share of successes
+            let convergence: Vec<_> = (1..=50)
+                .map(|m| {
+                    let h = Hipergeometrijska::new(h.s * m, h.r, h.n * m);
+                    (
+                        h.n as f64,
+                        approximation_errors(&h, &hypergeometric_approximations(&h)),
+                    )
+                })
+                .collect();
+            let errors = approximation_errors(&h, &approximations);
+            (approximations, errors, convergence, "velikost populacije")
+        }
+        _ => return None,
+    };
+    let k_min = d.kvantil(0.0005);
+    let k_max = d.kvantil(0.9995).min(k_min + 1000.0);
+    let ks: Vec<u64> = (k_min as u64..=k_max as u64).collect();
+
+    let normal_pdf = if d.var() > 0.0 {
+        let normal = Normalna::new(d.e(), d.var());
+        (0..=500)
+            .map(|i| {
+                let x = k_min - 1.0 + (k_max - k_min + 2.0) * i as f64 / 500.0;
+                [x, normal.pdf(x)]
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let mut series: Vec<(&'static str, Vec<[f64; 2]>)> =
+        errors.iter().map(|(name, _)| (*name, vec![])).collect();
+    for (size, errors) in &convergence {
+        for (name, error) in errors {
+            if let Some((_, points)) = series.iter_mut().find(|(n, _)| n == name) {
+                points.push([*size, error.cdf.max(1e-16).log10()]);
+            }
+        }
+    }
+
+    Some(ApproxData {
+        approximations: approximations
+            .iter()
+            .zip(&errors)
+            .map(|((name, approximation), (_, error))| Approximation {
+                name,
+                points: ks
+                    .iter()
+                    .map(|&k| [k as f64, approximation.pmf(k)])
+                    .collect(),
+                error: *error,
+            })
+            .collect(),
+        normal_pdf,
+        convergence: series,
+        size,
+    })
+}
+
+// numeric columns of a CSV file; the delimiter is ';', a tab or ',', with ';' decimal commas are allowed;
+// the first row is a header if any of its cells is not a number, empty or invalid cells are skipped
+fn parse_csv(text: &str) -> Result<Vec<(String, Vec<f64>)>, String> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let first = lines.next().ok_or("datoteka je prazna")?;
+    let delimiter = [';', '\t', ',']
+        .into_iter()
+        .find(|&c| first.contains(c))
+        .unwrap_or(',');
+    let number = |cell: &str| {
+        let cell = cell.trim().trim_matches('"');
+        let cell = if delimiter == ',' {
+            cell.to_string()
+        } else {
+            cell.replace(',', ".")
+        };
+        cell.parse::<f64>().ok().filter(|x| x.is_finite())
+    };
+
+    let cells: Vec<&str> = first.split(delimiter).collect();
+    let header = cells.iter().any(|cell| number(cell).is_none());
+    let mut columns: Vec<(String, Vec<f64>)> = cells
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| {
+            let name = if header {
+                cell.trim().trim_matches('"').to_string()
+            } else {
+                format!("stolpec {}", i + 1)
+            };
+            (name, vec![])
+        })
+        .collect();
+
+    let rows = if header { None } else { Some(first) };
+    for line in rows.into_iter().chain(lines) {
+        for (column, cell) in columns.iter_mut().zip(line.split(delimiter)) {
+            if let Some(x) = number(cell) {
+                column.1.push(x);
+            }
+        }
+    }
+
+    columns.retain(|(_, values)| !values.is_empty());
+    if columns.is_empty() {
+        return Err("v datoteki ni številskih stolpcev".to_string());
+    }
+    Ok(columns)
+}
+
+fn sort_fits(fits: &mut [Prileganje], criterion: Kriterij) {
+    fits.sort_by(|a, b| a.vrednost(criterion).total_cmp(&b.vrednost(criterion)));
+}
+
+// bars of a density histogram, so they are comparable with a pdf; values outside [lo, hi] are counted but not drawn
+fn data_range(data: &[f64]) -> (f64, f64) {
+    let lo = data.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = data.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (lo, hi)
+}
+
+// counts have relative frequency bars at each value, other data a density histogram
+fn data_bars(data: &[f64], bins: usize) -> Vec<Bar> {
+    let (lo, hi) = data_range(data);
+    if data.iter().all(|&x| x >= 0.0 && x.fract() == 0.0) && hi - lo <= 1000.0 {
+        let mut counts = vec![0usize; (hi - lo) as usize + 1];
+        for &x in data {
+            counts[(x - lo) as usize] += 1;
+        }
+        counts
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Bar::new(lo + i as f64, c as f64 / data.len() as f64).width(0.6))
+            .collect()
+    } else if hi > lo {
+        histogram(data, lo, hi + (hi - lo) * 1e-9, bins)
+    } else {
+        vec![]
+    }
+}
+
+fn histogram(values: &[f64], lo: f64, hi: f64, bins: usize) -> Vec<Bar> {
+    let width = (hi - lo) / bins as f64;
+    let mut counts = vec![0usize; bins];
+    for &x in values {
+        if x >= lo && x < hi {
+            counts[(((x - lo) / width) as usize).min(bins - 1)] += 1;
+        }
+    }
+    let scale = 1.0 / (values.len().max(1) as f64 * width);
+    counts
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| Bar::new(lo + (i as f64 + 0.5) * width, c as f64 * scale).width(width))
+        .collect()
+}
+
+// pmf at the nearest integer for discrete distributions, pdf otherwise
+fn hover_label(d: &dyn DynPorazdelitev, x: f64) -> String {
+    if d.diskretna() {
+        let k = x.round().max(0.0);
+        format!(
+            "k = {}\npmf = {:.6}\ncdf = {:.6}",
+            k,
+            d.gostota(k),
+            d.cdf(k)
+        )
+    } else {
+        format!(
+            "x = {:.4}\npdf = {:.6}\ncdf = {:.6}",
+            x,
+            d.gostota(x),
+            d.cdf(x)
+        )
+    }
+}
+
+fn presets_path() -> Option<std::path::PathBuf> {
+    eframe::storage_dir(APP_ID).map(|dir| dir.join(PRESETS_FILE))
+}
+
+// "name = spec" per line, the name may itself contain "=" outside of parentheses;
+// blank lines and lines starting with # are skipped, invalid lines are reported
+fn parse_presets(text: &str) -> (Vec<Preset>, Vec<String>) {
+    let (mut presets, mut errors) = (vec![], vec![]);
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut depth = 0i32;
+        let mut split = None;
+        for (j, c) in line.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                '=' if depth == 0 => split = Some(j),
+                _ => {}
+            }
+        }
+        let Some(j) = split else {
+            errors.push(format!("vrstica {}: manjka \"ime = porazdelitev\"", i + 1));
+            continue;
+        };
+        let (name, spec) = (line[..j].trim(), line[j + 1..].trim());
+        match razcleni(spec) {
+            Ok(_) if !name.is_empty() => presets.push(Preset {
+                name: name.to_string(),
+                spec: spec.to_string(),
+            }),
+            Ok(_) => errors.push(format!("vrstica {}: manjka ime", i + 1)),
+            Err(err) => errors.push(format!("vrstica {}: {}", i + 1, err)),
+        }
+    }
+    (presets, errors)
+}
+
+// names that parse_presets would not read back: a leading # starts a comment and the separating
+// "=" is only looked for outside of parentheses
+fn preset_name_error(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        return Some("ime je prazno");
+    }
+    if name.starts_with('#') {
+        return Some("ime se ne sme začeti z #");
+    }
+    let mut depth = 0i32;
+    for c in name.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if depth < 0 {
+            break;
+        }
+    }
+    if depth != 0 {
+        return Some("oklepaji v imenu niso uravnoteženi");
+    }
+    None
+}
+
+fn format_presets(presets: &[Preset]) -> String {
+    let mut text = "# prednastavitve: ime = porazdelitev, npr. naloga 3 = Hipergeometrijska(s=4, r=10, n=52)\n".to_string();
+    for preset in presets {
+        text += &format!("{} = {}\n", preset.name, preset.spec);
+    }
+    text
+}
+
 fn main() -> eframe::Result<()> {
     let options = eframe::NativeOptions::default();
     eframe::run_native(
-        "VERJETNOST",
+        APP_ID,
         options,
-        Box::new(|_cc| {
-            Ok(Box::<MyApp>::default())
+        Box::new(|cc| {
+            let mut app = MyApp::default();
+            if let Some(state) = cc
+                .storage
+                .and_then(|storage| eframe::get_value::<SavedState>(storage, eframe::APP_KEY))
+            {
+                app.restore(state);
+                cc.egui_ctx.set_theme(app.theme);
+            }
+            app.load_presets();
+            app.update_info();
+            Ok(Box::new(app))
         }),
     )
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the hover label is computed every frame while the mouse is over the plot, and plots
+    // can be panned far beyond the support
+    #[test]
+    fn hover_label_far_from_support() {
+        for entry in &register::REGISTER {
+            let d = (entry.ustvari)(&entry.privzeti_parametri()).unwrap();
+            for x in [1e30, f64::MAX] {
+                let label = hover_label(d.as_ref(), x);
+                assert!(
+                    label.ends_with("cdf = 1.000000"),
+                    "{}: {}",
+                    entry.ime,
+                    label
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parse_csv_delimiters_and_headers() {
+        let columns = parse_csv("x,y\n1,2\n3,4\n").unwrap();
+        assert_eq!(
+            columns,
+            vec![
+                ("x".to_string(), vec![1.0, 3.0]),
+                ("y".to_string(), vec![2.0, 4.0])
+            ]
+        );
+
+        // with ';' the decimal separator may be a comma
+        let columns = parse_csv("\"a\";\"b\"\n1,5;2\n3;4,25\n").unwrap();
+        assert_eq!(
+            columns,
+            vec![
+                ("a".to_string(), vec![1.5, 3.0]),
+                ("b".to_string(), vec![2.0, 4.25])
+            ]
+        );
+
+        // a numeric first row is data, not a header
+        let columns = parse_csv("1\t2\n\n3\t4").unwrap();
+        assert_eq!(columns[0], ("stolpec 1".to_string(), vec![1.0, 3.0]));
+        assert_eq!(columns[1], ("stolpec 2".to_string(), vec![2.0, 4.0]));
+
+        // empty and invalid cells are skipped, text columns dropped
+        let columns = parse_csv("name,value\nA,1\nB,\nC,x\nD,2e3").unwrap();
+        assert_eq!(columns, vec![("value".to_string(), vec![1.0, 2000.0])]);
+
+        assert!(parse_csv("").is_err());
+        assert!(parse_csv("a,b\nx,y").is_err());
+    }
+
+    #[test]
+    fn histogram_is_a_density() {
+        let bars = histogram(&[0.0, 0.5, 1.0, 1.5, 1.75, 2.0, 9.0, -1.0], 0.0, 2.0, 2);
+        assert_eq!(bars.len(), 2);
+        assert_eq!((bars[0].argument, bars[0].bar_width), (0.5, 1.0));
+        assert_eq!((bars[1].argument, bars[1].bar_width), (1.5, 1.0));
+        // values outside [lo, hi) count towards n, so the bars hold 5/8 of the mass
+        assert_eq!(bars[0].value, 2.0 / 8.0);
+        assert_eq!(bars[1].value, 3.0 / 8.0);
+        assert!(
+            histogram(&[], 0.0, 1.0, 4)
+                .iter()
+                .all(|bar| bar.value == 0.0)
+        );
+    }
+
+    #[test]
+    fn data_bars_count_each_value() {
+        let bars = data_bars(&[3.0, 5.0, 3.0, 3.0], 10);
+        let bars: Vec<(f64, f64)> = bars.iter().map(|bar| (bar.argument, bar.value)).collect();
+        assert_eq!(bars, vec![(3.0, 0.75), (4.0, 0.0), (5.0, 0.25)]);
+        // wide counts get a histogram with the chosen number of bins
+        assert_eq!(data_bars(&[0.0, 1e9], 10).len(), 10);
+        assert_eq!(data_bars(&[0.5, 1.5, 2.5], 4).len(), 4);
+        assert!(data_bars(&[2.5, 2.5], 4).is_empty());
+    }
+
+    #[test]
+    fn parse_presets_lines() {
+        let text = "# comment\n\nnaloga 3 = Hipergeometrijska(s=4, r=10, n=52)\n  a = b = Poisson(lambda=2)  \nPoisson(1)\n = Poisson(1)\nx = Poisson(-1)\n";
+        let (presets, errors) = parse_presets(text);
+        assert_eq!(
+            presets,
+            vec![
+                Preset {
+                    name: "naloga 3".to_string(),
+                    spec: "Hipergeometrijska(s=4, r=10, n=52)".to_string()
+                },
+                Preset {
+                    name: "a = b".to_string(),
+                    spec: "Poisson(lambda=2)".to_string()
+                },
+            ]
+        );
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("vrstica 5:"));
+        assert!(errors[1].starts_with("vrstica 6:"));
+        assert!(errors[2].starts_with("vrstica 7:"));
+    }
+
+    #[test]
+    fn presets_round_trip() {
+        let presets: Vec<Preset> = ["naloga 3", "a = b", "f(x) = 1", "x (y)", "50% #2"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                assert_eq!(preset_name_error(name), None);
+                Preset {
+                    name: name.to_string(),
+                    spec: Binomska::new(i as u64 + 1, 0.3).to_string(),
+                }
+            })
+            .collect();
+        let (parsed, errors) = parse_presets(&format_presets(&presets));
+        assert!(errors.is_empty());
+        assert_eq!(parsed, presets);
+
+        // names that would be lost on reload are rejected
+        for name in ["", "#1", "a(b", "a)b(", "f(x"] {
+            assert!(preset_name_error(name).is_some(), "{}", name);
+        }
+    }
+}
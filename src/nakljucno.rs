@@ -0,0 +1,50 @@
+// xoshiro256** (Blackman, Vigna), stanje inicializiramo s splitmix64
+#[derive(Debug, Clone)]
+pub struct Generator {
+    stanje: [u64; 4],
+}
+
+impl Generator {
+    pub fn new(seme: u64) -> Self {
+        let mut z = seme;
+        let mut stanje = [0; 4];
+        for s in stanje.iter_mut() {
+            z = z.wrapping_add(0x9e3779b97f4a7c15);
+            let mut x = z;
+            x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+            *s = x ^ (x >> 31);
+        }
+        Generator { stanje }
+    }
+
+    // seme vzamemo iz sistemske ure
+    #[cfg(feature = "std")]
+    pub fn iz_ure() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Generator::new(nanos)
+    }
+
+    pub fn naslednje(&mut self) -> u64 {
+        let s = &mut self.stanje;
+        let rezultat = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+
+        rezultat
+    }
+
+    // enakomerno na (0, 1), da sta ln(u) in kvantil(u) vedno končna
+    pub fn enakomerno(&mut self) -> f64 {
+        ((self.naslednje() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+}
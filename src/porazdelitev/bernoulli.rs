@@ -1,17 +1,30 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
 use crate::porazdelitev::*;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
 pub struct Bernoulli {
     pub p: f64,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub q: f64,
 }
 
 impl Bernoulli {
     pub fn new(p: f64) -> Self {
-        assert!(p >= 0.0 && p <= 1.0);
+        assert!((0.0..=1.0).contains(&p));
         Bernoulli { p, q: 1.0 - p }
     }
 }
 
+impl fmt::Display for Bernoulli {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bernoulli(p={})", self.p)
+    }
+}
+
 impl Porazdelitev for Bernoulli {
     fn cdf(&self, x: f64) -> f64 {
         if x < 0.0 {
@@ -45,3 +58,16 @@ impl Varianca for Bernoulli {
         self.p * self.q
     }
 }
+
+impl Kvantil for Bernoulli {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        if p <= self.q { 0.0 } else { 1.0 }
+    }
+}
+
+impl Vzorcenje for Bernoulli {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
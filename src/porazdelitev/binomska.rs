@@ -1,25 +1,54 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
 use crate::porazdelitev::*;
 use crate::utils;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
 pub struct Binomska {
     pub n: u64,
     pub p: f64,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub q: f64,
 }
 
 impl Binomska {
     pub fn new(n: u64, p: f64) -> Self {
-        assert!(p >= 0.0 && p <= 1.0);
+        assert!((0.0..=1.0).contains(&p));
 
         Binomska { n, p, q: 1.0 - p }
     }
 }
 
+impl fmt::Display for Binomska {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Binomska(n={}, p={})", self.n, self.p)
+    }
+}
+
 impl Diskretna for Binomska {
     fn pmf(&self, x: u64) -> f64 {
-        let bin = utils::binomial(self.n, x) as f64;
-
-        bin * self.p.powi(x as i32) * self.q.powi((self.n - x) as i32)
+        if x > self.n {
+            return 0.0;
+        }
+        // pri majhnih n neposredno, kar je natančneje
+        if self.n <= 170 {
+            let bin = utils::binomial(self.n, x);
+            return bin * self.p.powi(x as i32) * self.q.powi((self.n - x) as i32);
+        }
+        // pri robnih p bi v logaritmih dobili 0 * ln 0 = NaN
+        if self.p == 0.0 || self.q == 0.0 {
+            let k = if self.p == 0.0 { 0 } else { self.n };
+            return if x == k { 1.0 } else { 0.0 };
+        }
+        // v logaritmih kot pri Poissonovi, sicer binomski koeficient in potence pri velikih n
+        // prekoračijo obseg f64
+        (utils::ln_binomial(self.n, x)
+            + x as f64 * self.p.ln()
+            + (self.n - x) as f64 * (-self.p).ln_1p())
+        .exp()
     }
 }
 
@@ -28,10 +57,16 @@ impl Porazdelitev for Binomska {
         if x < 0.0 {
             return 0.0;
         }
-        let k = x.floor() as u64;
+        // nad n je cdf 1, za velike x bi floor(x) as u64 sicer seštevali do u64::MAX
+        let k = (x.floor() as u64).min(self.n);
 
         utils::sestej(|i: u64| <Binomska as Diskretna>::pmf(self, i), k)
     }
+
+    fn cdf_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        let kumulativna = utils::kumulativna_tabela(|i| self.pmf(i), x, self.n);
+        utils::iz_kumulativne(&kumulativna, x, izhod);
+    }
 }
 
 impl PricakovanaVrednost for Binomska {
@@ -45,3 +80,17 @@ impl Varianca for Binomska {
         (self.n as f64) * self.p * self.q
     }
 }
+
+impl Kvantil for Binomska {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        let od = utils::zacetek_vsote(self.e(), self.var(), 0);
+        utils::kvantil_diskretne(|i| self.pmf(i), p, od, self.n) as f64
+    }
+}
+
+impl Vzorcenje for Binomska {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
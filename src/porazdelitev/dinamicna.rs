@@ -0,0 +1,235 @@
+use core::fmt;
+
+use crate::porazdelitev::*;
+
+// skupni objektno varni vmesnik, da lahko z različnimi porazdelitvami delamo prek Box<dyn _>;
+// Send in Sync, da lahko Box<dyn _> pošljemo v drugo nit; Display mora dati zapis, ki ga
+// prebere razcleni
+pub trait DynPorazdelitev:
+    PricakovanaVrednost
+    + Varianca
+    + Kvantil
+    + Vzorcenje
+    + VerjetnostIntervala
+    + fmt::Display
+    + Send
+    + Sync
+{
+    // ime v registru
+    fn ime(&self) -> &'static str;
+
+    // vrednosti parametrov v vrstnem redu, kot ga določa register
+    fn parametri(&self) -> Vec<f64>;
+
+    fn diskretna(&self) -> bool;
+
+    // pmf pri diskretnih (0 v necelih točkah), pdf pri zveznih
+    fn gostota(&self, x: f64) -> f64;
+
+    fn gostota_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        assert_eq!(x.len(), izhod.len());
+        for (x, y) in x.iter().zip(izhod.iter_mut()) {
+            *y = self.gostota(*x);
+        }
+    }
+}
+
+// pmf v x, kjer je x poljubno realno število
+fn pmf_v<D: Diskretna>(d: &D, x: f64) -> f64 {
+    if x < 0.0 || x.fract() != 0.0 {
+        return 0.0;
+    }
+    d.pmf(x as u64)
+}
+
+impl DynPorazdelitev for Bernoulli {
+    fn ime(&self) -> &'static str {
+        "Bernoulli"
+    }
+
+    fn parametri(&self) -> Vec<f64> {
+        vec![self.p]
+    }
+
+    fn diskretna(&self) -> bool {
+        true
+    }
+
+    fn gostota(&self, x: f64) -> f64 {
+        pmf_v(self, x)
+    }
+}
+
+impl DynPorazdelitev for Binomska {
+    fn ime(&self) -> &'static str {
+        "Binomska"
+    }
+
+    fn parametri(&self) -> Vec<f64> {
+        vec![self.n as f64, self.p]
+    }
+
+    fn diskretna(&self) -> bool {
+        true
+    }
+
+    fn gostota(&self, x: f64) -> f64 {
+        pmf_v(self, x)
+    }
+}
+
+impl DynPorazdelitev for Eksponentna {
+    fn ime(&self) -> &'static str {
+        "Eksponentna"
+    }
+
+    fn parametri(&self) -> Vec<f64> {
+        vec![self.lambda]
+    }
+
+    fn diskretna(&self) -> bool {
+        false
+    }
+
+    fn gostota(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    fn gostota_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        self.pdf_batch(x, izhod)
+    }
+}
+
+impl DynPorazdelitev for EnakomernaInterval {
+    fn ime(&self) -> &'static str {
+        "EnakomernaInterval"
+    }
+
+    fn parametri(&self) -> Vec<f64> {
+        vec![self.a, self.b]
+    }
+
+    fn diskretna(&self) -> bool {
+        false
+    }
+
+    fn gostota(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    fn gostota_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        self.pdf_batch(x, izhod)
+    }
+}
+
+impl DynPorazdelitev for Geometrijska {
+    fn ime(&self) -> &'static str {
+        "Geometrijska"
+    }
+
+    fn parametri(&self) -> Vec<f64> {
+        vec![self.p]
+    }
+
+    fn diskretna(&self) -> bool {
+        true
+    }
+
+    fn gostota(&self, x: f64) -> f64 {
+        pmf_v(self, x)
+    }
+}
+
+impl DynPorazdelitev for HiKvadrat {
+    fn ime(&self) -> &'static str {
+        "HiKvadrat"
+    }
+
+    fn parametri(&self) -> Vec<f64> {
+        vec![self.k]
+    }
+
+    fn diskretna(&self) -> bool {
+        false
+    }
+
+    fn gostota(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+}
+
+impl DynPorazdelitev for Hipergeometrijska {
+    fn ime(&self) -> &'static str {
+        "Hipergeometrijska"
+    }
+
+    fn parametri(&self) -> Vec<f64> {
+        vec![self.s as f64, self.r as f64, self.n as f64]
+    }
+
+    fn diskretna(&self) -> bool {
+        true
+    }
+
+    fn gostota(&self, x: f64) -> f64 {
+        pmf_v(self, x)
+    }
+}
+
+impl DynPorazdelitev for Normalna {
+    fn ime(&self) -> &'static str {
+        "Normalna"
+    }
+
+    fn parametri(&self) -> Vec<f64> {
+        vec![self.mu, self.sigma2]
+    }
+
+    fn diskretna(&self) -> bool {
+        false
+    }
+
+    fn gostota(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    fn gostota_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        self.pdf_batch(x, izhod)
+    }
+}
+
+impl DynPorazdelitev for Poisson {
+    fn ime(&self) -> &'static str {
+        "Poisson"
+    }
+
+    fn parametri(&self) -> Vec<f64> {
+        vec![self.lambda]
+    }
+
+    fn diskretna(&self) -> bool {
+        true
+    }
+
+    fn gostota(&self, x: f64) -> f64 {
+        pmf_v(self, x)
+    }
+}
+
+impl DynPorazdelitev for StudentovaT {
+    fn ime(&self) -> &'static str {
+        "StudentovaT"
+    }
+
+    fn parametri(&self) -> Vec<f64> {
+        vec![self.n]
+    }
+
+    fn diskretna(&self) -> bool {
+        false
+    }
+
+    fn gostota(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+}
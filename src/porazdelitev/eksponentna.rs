@@ -1,5 +1,11 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
 use crate::porazdelitev::*;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
 pub struct Eksponentna {
     pub lambda: f64,
 }
@@ -11,6 +17,12 @@ impl Eksponentna {
     }
 }
 
+impl fmt::Display for Eksponentna {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Eksponentna(lambda={})", self.lambda)
+    }
+}
+
 impl Porazdelitev for Eksponentna {
     fn cdf(&self, x: f64) -> f64 {
         if x < 0.0 {
@@ -19,6 +31,14 @@ impl Porazdelitev for Eksponentna {
 
         1.0 - (-self.lambda * x).exp()
     }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 1.0;
+        }
+
+        (-self.lambda * x).exp()
+    }
 }
 
 impl Zvezna for Eksponentna {
@@ -29,6 +49,15 @@ impl Zvezna for Eksponentna {
 
         self.lambda * (-self.lambda * x).exp()
     }
+
+    fn pdf_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        assert_eq!(x.len(), izhod.len());
+        let lambda = self.lambda;
+        for (x, y) in x.iter().zip(izhod.iter_mut()) {
+            let gostota = lambda * (-lambda * x).exp();
+            *y = if *x < 0.0 { 0.0 } else { gostota };
+        }
+    }
 }
 
 impl PricakovanaVrednost for Eksponentna {
@@ -42,3 +71,21 @@ impl Varianca for Eksponentna {
         1.0 / self.lambda.powi(2)
     }
 }
+
+impl Kvantil for Eksponentna {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        -(-p).ln_1p() / self.lambda
+    }
+
+    fn isf(&self, q: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&q));
+        -q.ln() / self.lambda
+    }
+}
+
+impl Vzorcenje for Eksponentna {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.isf(g.enakomerno())
+    }
+}
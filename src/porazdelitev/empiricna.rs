@@ -0,0 +1,220 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
+use crate::porazdelitev::*;
+
+// načini računanja kvantilov vzorca (Hyndman, Fan, Sample Quantiles in Statistical Packages, 1996)
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolacija {
+    // inverz stopničaste ECDF (tip 1)
+    Inverzna,
+    // linearna med x_(k) pri p = (k - 1) / (n - 1) (tip 7)
+    Linearna,
+    // linearna med x_(k) pri p = (k - 1/2) / n (tip 5)
+    Hazen,
+    // linearna med x_(k) pri p = k / (n + 1) (tip 6)
+    Weibull,
+    // linearna med x_(k) pri p = (k - 1/3) / (n + 1/3), mediansko nepristranska (tip 8)
+    Mediana,
+}
+
+// porazdelitev, ki vsaki točki vzorca dodeli verjetnost 1/n
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
+pub struct Empiricna {
+    // urejeni podatki, zasebni, ker cdf in kvantil računata z bisekcijo
+    podatki: Vec<f64>,
+    pub interpolacija: Interpolacija,
+}
+
+impl Empiricna {
+    pub fn new(podatki: &[f64]) -> Self {
+        assert!(!podatki.is_empty());
+        assert!(podatki.iter().all(|x| !x.is_nan()));
+
+        let mut podatki = podatki.to_vec();
+        podatki.sort_by(|a, b| a.total_cmp(b));
+        Empiricna {
+            podatki,
+            interpolacija: Interpolacija::Inverzna,
+        }
+    }
+
+    // naraščajoče urejeni
+    pub fn podatki(&self) -> &[f64] {
+        &self.podatki
+    }
+
+    pub fn n(&self) -> usize {
+        self.podatki.len()
+    }
+
+    // Kolmogorov-Smirnovova razdalja sup |F_n(x) - F(x)| do (zvezne) porazdelitve
+    pub fn ks_statistika<D: Porazdelitev + ?Sized>(&self, d: &D) -> f64 {
+        let n = self.n() as f64;
+        self.podatki
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| {
+                let f = d.cdf(x);
+                ((i + 1) as f64 / n - f).max(f - i as f64 / n)
+            })
+            .fold(0.0, f64::max)
+    }
+
+    // b ponovitev statistike na vzorcih s ponavljanjem (bootstrap)
+    pub fn bootstrap<F>(&self, g: &mut Generator, b: usize, statistika: F) -> Vec<f64>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let mut vzorec = vec![0.0; self.n()];
+        (0..b)
+            .map(|_| {
+                for x in vzorec.iter_mut() {
+                    *x = self.vzorec(g);
+                }
+                statistika(&vzorec)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Empiricna {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Empiricna(n={})", self.n())
+    }
+}
+
+impl Porazdelitev for Empiricna {
+    fn cdf(&self, x: f64) -> f64 {
+        self.podatki.partition_point(|&y| y <= x) as f64 / self.n() as f64
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        (self.n() - self.podatki.partition_point(|&y| y <= x)) as f64 / self.n() as f64
+    }
+}
+
+impl PricakovanaVrednost for Empiricna {
+    fn e(&self) -> f64 {
+        self.podatki.iter().sum::<f64>() / self.n() as f64
+    }
+}
+
+// varianca porazdelitve, torej deljena z n in ne z n - 1
+impl Varianca for Empiricna {
+    fn var(&self) -> f64 {
+        let e = self.e();
+        self.podatki.iter().map(|x| (x - e).powi(2)).sum::<f64>() / self.n() as f64
+    }
+}
+
+impl Kvantil for Empiricna {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        let x = &self.podatki;
+        let n = x.len() as f64;
+
+        // h je (od 1 štet) položaj kvantila med urejenimi podatki
+        let h = match self.interpolacija {
+            Interpolacija::Inverzna => return x[((n * p).ceil() as usize).clamp(1, x.len()) - 1],
+            Interpolacija::Linearna => (n - 1.0) * p + 1.0,
+            Interpolacija::Hazen => n * p + 0.5,
+            Interpolacija::Weibull => (n + 1.0) * p,
+            Interpolacija::Mediana => (n + 1.0 / 3.0) * p + 1.0 / 3.0,
+        };
+        let h = h.clamp(1.0, n);
+        let k = h.floor() as usize;
+        if k == x.len() {
+            return x[k - 1];
+        }
+        x[k - 1] + (h - k as f64) * (x[k] - x[k - 1])
+    }
+}
+
+// vzorčenje iz empirične porazdelitve je izbira naključne točke vzorca
+impl Vzorcenje for Empiricna {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        let i = (g.enakomerno() * self.n() as f64) as usize;
+        self.podatki[i.min(self.n() - 1)]
+    }
+}
+
+impl Okrnljiva for Empiricna {
+    fn okrnjeni_momenti(&self, a: f64, b: f64) -> (f64, f64) {
+        let od = self.podatki.partition_point(|&y| y <= a);
+        let do_ = self.podatki.partition_point(|&y| y <= b);
+        let okrnjena = Empiricna::new(&self.podatki[od..do_]);
+        (okrnjena.e(), okrnjena.var())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ecdf() {
+        let e = Empiricna::new(&[3.0, 2.0, 1.0, 2.0]);
+        assert_eq!(e.podatki(), &[1.0, 2.0, 2.0, 3.0]);
+        assert_eq!(e.cdf(0.5), 0.0);
+        assert_eq!(e.cdf(1.999), 0.25);
+        // skok v točki vzorca je vključen, ponovljena točka šteje dvakrat
+        assert_eq!(e.cdf(2.0), 0.75);
+        assert_eq!(e.sf(2.0), 0.25);
+        assert_eq!(e.cdf(3.0), 1.0);
+        assert_eq!((e.e(), e.var()), (2.0, 0.5));
+    }
+
+    #[test]
+    fn test_interpolacija() {
+        // vrednosti se ujemajo s quantile(c(2, 4, 7, 11, 16), 0.3, type = k) v R
+        let mut e = Empiricna::new(&[16.0, 2.0, 11.0, 4.0, 7.0]);
+        for (interpolacija, pricakovana) in [
+            (Interpolacija::Inverzna, 4.0),
+            (Interpolacija::Linearna, 4.6),
+            (Interpolacija::Hazen, 4.0),
+            (Interpolacija::Weibull, 3.6),
+            (Interpolacija::Mediana, 3.8666666666666667),
+        ] {
+            e.interpolacija = interpolacija;
+            assert!((e.kvantil(0.3) - pricakovana).abs() < 1e-12);
+            assert_eq!((e.kvantil(0.0), e.kvantil(1.0)), (2.0, 16.0));
+        }
+    }
+
+    #[test]
+    fn test_ks_statistika() {
+        let u = EnakomernaInterval::new(0.0, 1.0);
+        assert_eq!(Empiricna::new(&[0.5]).ks_statistika(&u), 0.5);
+        assert_eq!(Empiricna::new(&[0.75, 0.25]).ks_statistika(&u), 0.25);
+        assert_eq!(Empiricna::new(&[2.0]).ks_statistika(&u), 1.0);
+    }
+
+    #[test]
+    fn test_bootstrap() {
+        let e = Empiricna::new(&[1.0, 4.0, 2.0, 8.0, 5.0, 7.0]);
+        let povprecje = |x: &[f64]| x.iter().sum::<f64>() / x.len() as f64;
+        let b = e.bootstrap(&mut Generator::new(11), 2000, povprecje);
+        assert_eq!(b, e.bootstrap(&mut Generator::new(11), 2000, povprecje));
+        assert!(b.iter().all(|&m| (1.0..=8.0).contains(&m)));
+        // povprečje povprečij je blizu e, varianca pa blizu var / n
+        let m = povprecje(&b);
+        let v = b.iter().map(|x| (x - m).powi(2)).sum::<f64>() / b.len() as f64;
+        assert!((m - e.e()).abs() < 0.1);
+        assert!((v / (e.var() / 6.0) - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_okrnjeni_momenti() {
+        let e = Empiricna::new(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        // (1, 4] vsebuje 2, 3 in 4
+        let (m, v) = e.okrnjeni_momenti(1.0, 4.0);
+        assert_eq!(m, 3.0);
+        assert!((v - 2.0 / 3.0).abs() < 1e-15);
+        let o = Okrnjena::new(e.clone(), 1.0, 4.0);
+        assert_eq!(o.e(), 3.0);
+    }
+}
@@ -1,5 +1,11 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
 use crate::porazdelitev::*;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
 pub struct EnakomernaInterval {
     pub a: f64,
     pub b: f64,
@@ -12,6 +18,12 @@ impl EnakomernaInterval {
     }
 }
 
+impl fmt::Display for EnakomernaInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EnakomernaInterval(a={}, b={})", self.a, self.b)
+    }
+}
+
 impl Porazdelitev for EnakomernaInterval {
     fn cdf(&self, x: f64) -> f64 {
         if x < self.a {
@@ -45,3 +57,16 @@ impl Varianca for EnakomernaInterval {
         (self.b - self.a).powi(2) / 12.0
     }
 }
+
+impl Kvantil for EnakomernaInterval {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        self.a + p * (self.b - self.a)
+    }
+}
+
+impl Vzorcenje for EnakomernaInterval {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
@@ -1,15 +1,33 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
 use crate::porazdelitev::*;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
 pub struct Geometrijska {
     pub p: f64,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub q: f64,
 }
 
 impl Geometrijska {
     pub fn new(p: f64) -> Self {
-        assert!(p >= 0.0 && p <= 1.0);
+        assert!((0.0..=1.0).contains(&p));
         Geometrijska { p, q: 1.0 - p }
     }
+
+    // ln q, natančno tudi pri majhnih p, kjer se 1 - p zaokroži na 1
+    fn ln_q(&self) -> f64 {
+        (-self.p).ln_1p()
+    }
+}
+
+impl fmt::Display for Geometrijska {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Geometrijska(p={})", self.p)
+    }
 }
 
 impl Porazdelitev for Geometrijska {
@@ -17,7 +35,15 @@ impl Porazdelitev for Geometrijska {
         if x < 1.0 {
             return 0.0;
         }
-        1.0 - self.q.powi(x.floor() as i32)
+        // q^k kot exp(k ln q); powi bi k pretvoril v i32
+        1.0 - (x.floor() * self.ln_q()).exp()
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x < 1.0 {
+            return 1.0;
+        }
+        (x.floor() * self.ln_q()).exp()
     }
 }
 
@@ -26,8 +52,12 @@ impl Diskretna for Geometrijska {
         if x == 0 {
             return 0.0;
         }
+        // pri p = 1 je ln q = -inf in 0 * ln q = NaN
+        if x == 1 {
+            return self.p;
+        }
 
-        self.p * self.q.powi((x - 1) as i32)
+        self.p * ((x - 1) as f64 * self.ln_q()).exp()
     }
 }
 
@@ -42,3 +72,28 @@ impl Varianca for Geometrijska {
         self.q / self.p.powi(2)
     }
 }
+
+impl Kvantil for Geometrijska {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        if p == 1.0 && self.q > 0.0 {
+            return f64::INFINITY;
+        }
+        if p == 0.0 || self.q == 0.0 {
+            return 1.0;
+        }
+        let k = ((-p).ln_1p() / self.ln_q()).ceil().max(1.0);
+        // popravimo morebitno zaokrožitveno napako
+        if k > 1.0 && self.cdf(k - 1.0) >= p {
+            k - 1.0
+        } else {
+            k
+        }
+    }
+}
+
+impl Vzorcenje for Geometrijska {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
@@ -0,0 +1,97 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
+use crate::porazdelitev::*;
+use crate::utils;
+
+// porazdelitev χ² s k prostostnimi stopnjami, k je lahko necelo
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
+pub struct HiKvadrat {
+    pub k: f64,
+}
+
+impl HiKvadrat {
+    pub fn new(k: f64) -> Self {
+        assert!(k > 0.0 && k.is_finite());
+        HiKvadrat { k }
+    }
+}
+
+impl fmt::Display for HiKvadrat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HiKvadrat(k={})", self.k)
+    }
+}
+
+impl Porazdelitev for HiKvadrat {
+    fn cdf(&self, x: f64) -> f64 {
+        1.0 - self.sf(x)
+    }
+
+    // Q(k / 2, x / 2)
+    fn sf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 1.0;
+        }
+        if x.is_infinite() {
+            return 0.0;
+        }
+        utils::gama_q(0.5 * self.k, 0.5 * x)
+    }
+}
+
+impl Zvezna for HiKvadrat {
+    fn pdf(&self, x: f64) -> f64 {
+        let k = 0.5 * self.k;
+        if x < 0.0 || (x == 0.0 && k > 1.0) {
+            return 0.0;
+        }
+        if x == 0.0 {
+            return if k == 1.0 { 0.5 } else { f64::INFINITY };
+        }
+        ((k - 1.0) * x.ln() - 0.5 * x - k * core::f64::consts::LN_2 - utils::ln_gama(k)).exp()
+    }
+}
+
+impl PricakovanaVrednost for HiKvadrat {
+    fn e(&self) -> f64 {
+        self.k
+    }
+}
+
+impl Varianca for HiKvadrat {
+    fn var(&self) -> f64 {
+        2.0 * self.k
+    }
+}
+
+impl Kvantil for HiKvadrat {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        self.isf(1.0 - p)
+    }
+
+    // v zgornjem repu iščemo ničlo sf, da ne izgubimo natančnosti pri q blizu 0
+    fn isf(&self, q: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&q));
+        if q == 0.0 {
+            return f64::INFINITY;
+        }
+        if q == 1.0 {
+            return 0.0;
+        }
+        let mut hi = self.k.max(1.0);
+        while self.sf(hi) > q {
+            hi *= 2.0;
+        }
+        utils::bisekcija(|x| q - self.sf(x), 0.0, hi)
+    }
+}
+
+impl Vzorcenje for HiKvadrat {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
@@ -1,6 +1,12 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
 use crate::porazdelitev::*;
 use crate::utils;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
 pub struct Hipergeometrijska {
     pub s: u64,
     pub r: u64,
@@ -14,10 +20,29 @@ impl Hipergeometrijska {
     }
 }
 
+impl fmt::Display for Hipergeometrijska {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Hipergeometrijska(s={}, r={}, n={})",
+            self.s, self.r, self.n
+        )
+    }
+}
+
 impl Diskretna for Hipergeometrijska {
     fn pmf(&self, x: u64) -> f64 {
-        ((utils::binomial(self.s, x) * utils::binomial(self.n - self.s, self.r - x)) as f64)
-            / (utils::binomial(self.n, self.r) as f64)
+        if x > self.r || x > self.s || self.r - x > self.n - self.s {
+            return 0.0;
+        }
+        if self.n <= 170 {
+            return (utils::binomial(self.s, x) * utils::binomial(self.n - self.s, self.r - x))
+                / utils::binomial(self.n, self.r);
+        }
+        // v logaritmih, da binomski koeficienti pri velikih n ne prekoračijo obsega
+        (utils::ln_binomial(self.s, x) + utils::ln_binomial(self.n - self.s, self.r - x)
+            - utils::ln_binomial(self.n, self.r))
+        .exp()
     }
 }
 
@@ -26,7 +51,8 @@ impl Porazdelitev for Hipergeometrijska {
         if x < 0.0 {
             return 0.0;
         }
-        let k = x.floor() as u64;
+        // nosilec je {0, ..., min(r, s)}
+        let k = (x.floor() as u64).min(self.r.min(self.s));
 
         utils::sestej(|i: u64| <Hipergeometrijska as Diskretna>::pmf(self, i), k)
     }
@@ -34,7 +60,8 @@ impl Porazdelitev for Hipergeometrijska {
 
 impl PricakovanaVrednost for Hipergeometrijska {
     fn e(&self) -> f64 {
-        ((self.r * self.s) as f64) / (self.n as f64)
+        // v f64, produkt u64 bi se pri velikih parametrih prelil
+        (self.r as f64) * (self.s as f64) / (self.n as f64)
     }
 }
 
@@ -46,3 +73,22 @@ impl Varianca for Hipergeometrijska {
         (r * s * (n - s) * (n - r)) / (n * n * (n - 1.0))
     }
 }
+
+impl Kvantil for Hipergeometrijska {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        let od = utils::zacetek_vsote(
+            self.e(),
+            self.var(),
+            (self.r + self.s).saturating_sub(self.n),
+        );
+        let do_ = self.r.min(self.s);
+        utils::kvantil_diskretne(|i| self.pmf(i), p, od, do_) as f64
+    }
+}
+
+impl Vzorcenje for Hipergeometrijska {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
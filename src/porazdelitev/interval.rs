@@ -0,0 +1,290 @@
+use crate::porazdelitev::okrnjena::{cdf_meja, masa, sf_meja};
+use crate::porazdelitev::*;
+
+// katera krajišča intervala štejemo zraven; pri zveznih porazdelitvah ni pomembno
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Vkljucenost {
+    // [a, b]
+    Zaprt,
+    // (a, b)
+    Odprt,
+    // (a, b], P(a < X <= b) = F(b) - F(a)
+    LevoOdprt,
+    // [a, b)
+    DesnoOdprt,
+}
+
+impl Vkljucenost {
+    pub fn vkljucuje_a(self) -> bool {
+        matches!(self, Vkljucenost::Zaprt | Vkljucenost::DesnoOdprt)
+    }
+
+    pub fn vkljucuje_b(self) -> bool {
+        matches!(self, Vkljucenost::Zaprt | Vkljucenost::LevoOdprt)
+    }
+
+    // ali x leži na intervalu med a in b
+    pub fn vsebuje(self, a: f64, b: f64, x: f64) -> bool {
+        let nad_a = if self.vkljucuje_a() { x >= a } else { x > a };
+        let pod_b = if self.vkljucuje_b() { x <= b } else { x < b };
+        nad_a && pod_b
+    }
+}
+
+pub trait VerjetnostIntervala: Porazdelitev {
+    // verjetnost, da X leži med a in b, meji sta lahko neskončni
+    fn verjetnost_intervala(&self, a: f64, b: f64, vkljucenost: Vkljucenost) -> f64;
+}
+
+// največ toliko členov pmf seštejemo, sicer računamo z razliko cdf ali sf
+const MAKS_CLENOV: f64 = 1e5;
+
+// v zgornjem repu odštevamo sf, da F(b) - F(a) ne izgubi vseh mest
+pub fn verjetnost_zvezne<D: Porazdelitev + ?Sized>(d: &D, a: f64, b: f64) -> f64 {
+    if a >= b {
+        return 0.0;
+    }
+    masa(d, a, b).0.max(0.0)
+}
+
+// sešteje pmf le v celih točkah znotraj intervala; pri dolgih intervalih računa s cdf in sf,
+// ki morata biti za velike x omejena na nosilec (glej cdf Poissonove in binomske)
+pub fn verjetnost_diskretne<D: Diskretna + ?Sized>(
+    d: &D,
+    a: f64,
+    b: f64,
+    vkljucenost: Vkljucenost,
+) -> f64 {
+    let od = if vkljucenost.vkljucuje_a() {
+        a.ceil()
+    } else {
+        a.floor() + 1.0
+    }
+    .max(0.0);
+    let do_ = if vkljucenost.vkljucuje_b() {
+        b.floor()
+    } else {
+        b.ceil() - 1.0
+    };
+    if od > do_ {
+        return 0.0;
+    }
+
+    if do_ - od < MAKS_CLENOV {
+        return (od as u64..=do_ as u64)
+            .map(|k| d.pmf(k))
+            .sum::<f64>()
+            .min(1.0);
+    }
+
+    // zgornji rep seštevamo, dokler členi padajo in so zanemarljivi
+    if cdf_meja(d, od - 1.0) > 0.5 {
+        let mut vsota = 0.0;
+        let mut prejsnji = f64::INFINITY;
+        let mut k = od as u64;
+        while (k as f64) <= do_ && ((k as f64) - od) < MAKS_CLENOV {
+            let clen = d.pmf(k);
+            if clen < prejsnji && clen <= vsota * 1e-17 {
+                return vsota.min(1.0);
+            }
+            vsota += clen;
+            prejsnji = clen;
+            k += 1;
+        }
+        return (sf_meja(d, od - 1.0) - sf_meja(d, do_)).max(0.0);
+    }
+    (cdf_meja(d, do_) - cdf_meja(d, od - 1.0)).max(0.0)
+}
+
+impl VerjetnostIntervala for Bernoulli {
+    fn verjetnost_intervala(&self, a: f64, b: f64, vkljucenost: Vkljucenost) -> f64 {
+        verjetnost_diskretne(self, a, b, vkljucenost)
+    }
+}
+
+impl VerjetnostIntervala for Binomska {
+    fn verjetnost_intervala(&self, a: f64, b: f64, vkljucenost: Vkljucenost) -> f64 {
+        verjetnost_diskretne(self, a, b, vkljucenost)
+    }
+}
+
+impl VerjetnostIntervala for Eksponentna {
+    fn verjetnost_intervala(&self, a: f64, b: f64, _: Vkljucenost) -> f64 {
+        verjetnost_zvezne(self, a, b)
+    }
+}
+
+impl VerjetnostIntervala for EnakomernaInterval {
+    fn verjetnost_intervala(&self, a: f64, b: f64, _: Vkljucenost) -> f64 {
+        verjetnost_zvezne(self, a, b)
+    }
+}
+
+impl VerjetnostIntervala for Geometrijska {
+    fn verjetnost_intervala(&self, a: f64, b: f64, vkljucenost: Vkljucenost) -> f64 {
+        verjetnost_diskretne(self, a, b, vkljucenost)
+    }
+}
+
+impl VerjetnostIntervala for Hipergeometrijska {
+    fn verjetnost_intervala(&self, a: f64, b: f64, vkljucenost: Vkljucenost) -> f64 {
+        verjetnost_diskretne(self, a, b, vkljucenost)
+    }
+}
+
+impl VerjetnostIntervala for Normalna {
+    fn verjetnost_intervala(&self, a: f64, b: f64, _: Vkljucenost) -> f64 {
+        verjetnost_zvezne(self, a, b)
+    }
+}
+
+impl VerjetnostIntervala for Poisson {
+    fn verjetnost_intervala(&self, a: f64, b: f64, vkljucenost: Vkljucenost) -> f64 {
+        verjetnost_diskretne(self, a, b, vkljucenost)
+    }
+}
+
+impl VerjetnostIntervala for StudentovaT {
+    fn verjetnost_intervala(&self, a: f64, b: f64, _: Vkljucenost) -> f64 {
+        verjetnost_zvezne(self, a, b)
+    }
+}
+
+impl VerjetnostIntervala for HiKvadrat {
+    fn verjetnost_intervala(&self, a: f64, b: f64, _: Vkljucenost) -> f64 {
+        verjetnost_zvezne(self, a, b)
+    }
+}
+
+impl VerjetnostIntervala for KoncnaDiskretna {
+    fn verjetnost_intervala(&self, a: f64, b: f64, vkljucenost: Vkljucenost) -> f64 {
+        verjetnost_diskretne(self, a, b, vkljucenost)
+    }
+}
+
+impl VerjetnostIntervala for JedrnaOcena {
+    fn verjetnost_intervala(&self, a: f64, b: f64, _: Vkljucenost) -> f64 {
+        verjetnost_zvezne(self, a, b)
+    }
+}
+
+// delež točk vzorca v intervalu, pri ponovljenih vrednostih so krajišča pomembna
+impl VerjetnostIntervala for Empiricna {
+    fn verjetnost_intervala(&self, a: f64, b: f64, vkljucenost: Vkljucenost) -> f64 {
+        let x = self.podatki();
+        let od = if vkljucenost.vkljucuje_a() {
+            x.partition_point(|&y| y < a)
+        } else {
+            x.partition_point(|&y| y <= a)
+        };
+        let do_ = if vkljucenost.vkljucuje_b() {
+            x.partition_point(|&y| y <= b)
+        } else {
+            x.partition_point(|&y| y < b)
+        };
+        do_.saturating_sub(od) as f64 / self.n() as f64
+    }
+}
+
+impl VerjetnostIntervala for Mesanica<dyn ZveznaKomponenta> {
+    fn verjetnost_intervala(&self, a: f64, b: f64, _: Vkljucenost) -> f64 {
+        verjetnost_zvezne(self, a, b)
+    }
+}
+
+impl VerjetnostIntervala for Mesanica<dyn DiskretnaKomponenta> {
+    fn verjetnost_intervala(&self, a: f64, b: f64, vkljucenost: Vkljucenost) -> f64 {
+        verjetnost_diskretne(self, a, b, vkljucenost)
+    }
+}
+
+impl<D: Zvezna + Kvantil> VerjetnostIntervala for Transformirana<D> {
+    fn verjetnost_intervala(&self, a: f64, b: f64, _: Vkljucenost) -> f64 {
+        verjetnost_zvezne(self, a, b)
+    }
+}
+
+impl<A: Zvezna + Kvantil, B: Zvezna + Kvantil> VerjetnostIntervala for Vsota<A, B> {
+    fn verjetnost_intervala(&self, a: f64, b: f64, _: Vkljucenost) -> f64 {
+        verjetnost_zvezne(self, a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diskretna_krajisca() {
+        let p = Poisson::new(3.0);
+        let vsota = p.pmf(2) + p.pmf(3) + p.pmf(4);
+        assert!((p.verjetnost_intervala(2.0, 4.0, Vkljucenost::Zaprt) - vsota).abs() < 1e-15);
+        assert_eq!(
+            p.verjetnost_intervala(2.0, 4.0, Vkljucenost::Odprt),
+            p.pmf(3)
+        );
+        assert!((p.verjetnost_intervala(1.5, 4.5, Vkljucenost::Odprt) - vsota).abs() < 1e-15);
+        assert_eq!(
+            p.verjetnost_intervala(3.0, 3.0, Vkljucenost::LevoOdprt),
+            0.0
+        );
+        assert_eq!(
+            p.verjetnost_intervala(3.0, 3.0, Vkljucenost::Zaprt),
+            p.pmf(3)
+        );
+        let cela = p.verjetnost_intervala(f64::NEG_INFINITY, f64::INFINITY, Vkljucenost::Zaprt);
+        assert!((cela - 1.0).abs() < 1e-12);
+
+        let e = Empiricna::new(&[1.0, 2.0, 2.0, 3.0]);
+        assert_eq!(
+            e.verjetnost_intervala(2.0, 3.0, Vkljucenost::LevoOdprt),
+            0.25
+        );
+        assert_eq!(
+            e.verjetnost_intervala(2.0, 3.0, Vkljucenost::DesnoOdprt),
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_zgornji_rep() {
+        // P(X > 10) = 7.6198530241605e-24 za standardno normalno
+        let n = Normalna::new(0.0, 1.0);
+        let p = n.verjetnost_intervala(10.0, f64::INFINITY, Vkljucenost::LevoOdprt);
+        assert!((p / 7.6198530241605e-24 - 1.0).abs() < 1e-10);
+
+        // pri Poissonovi s λ = 1 je P(X >= 30) ~ 1e-33, 1 - F bi dal 0
+        let p = Poisson::new(1.0);
+        let rep = p.verjetnost_intervala(30.0, f64::INFINITY, Vkljucenost::Zaprt);
+        let vsota: f64 = (30..60).map(|k| p.pmf(k)).sum();
+        assert!((rep / vsota - 1.0).abs() < 1e-12);
+    }
+
+    // dolgi intervali z velikim končnim b ne smejo seštevati do b
+    #[test]
+    fn test_velik_b() {
+        let mesanica: Mesanica<dyn DiskretnaKomponenta> = Mesanica::new(vec![
+            (1.0, Box::new(Poisson::new(3.0))),
+            (1.0, Box::new(Binomska::new(10, 0.5))),
+        ]);
+        let porazdelitve: [&dyn Diskretna; 7] = [
+            &Poisson::new(3.0),
+            &Binomska::new(10, 0.5),
+            &Hipergeometrijska::new(5, 3, 20),
+            &Geometrijska::new(0.1),
+            &KoncnaDiskretna::new(vec![1.0, 2.0, 3.0]),
+            &mesanica,
+            &Okrnjena::new(Poisson::new(3.0), 1.0, f64::INFINITY),
+        ];
+        for d in porazdelitve {
+            for b in [1e12, 1e30, f64::MAX] {
+                let p = verjetnost_diskretne(d, 0.0, b, Vkljucenost::Zaprt);
+                assert!((p - 1.0).abs() < 1e-12);
+                let p = verjetnost_diskretne(d, 2.0, b, Vkljucenost::Zaprt);
+                assert!((p - d.sf(1.0)).abs() < 1e-12);
+            }
+        }
+        let p = Poisson::new(3.0).verjetnost_intervala(0.0, 1e12, Vkljucenost::Zaprt);
+        assert!((p - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,363 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
+use crate::porazdelitev::*;
+use crate::utils;
+use core::f64::consts::{PI, SQRT_2};
+
+// obe jedri imata varianco 1, da pravila za širino pasu veljajo za obe
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Jedro {
+    Gaussovo,
+    // 3 / (4 sqrt 5) (1 - u^2 / 5) na [-sqrt 5, sqrt 5]
+    Epanechnikovo,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SirinaPasu {
+    // 0.9 min(s, IQR / 1.34) n^(-1/5)
+    Silverman,
+    // 1.06 s n^(-1/5)
+    Scott,
+    // maksimizira verjetje z izpustitvijo ene točke (leave-one-out); vsaka poskusna širina
+    // stane O(n^2), zato jo pri več kot MAKS_PRECNO točkah izberemo na podvzorcu
+    PrecnoPreverjanje,
+    Fiksna(f64),
+}
+
+const SQRT_5: f64 = 2.23606797749979;
+
+impl Jedro {
+    fn k(&self, u: f64) -> f64 {
+        match self {
+            Jedro::Gaussovo => (-0.5 * u * u).exp() / (2.0 * PI).sqrt(),
+            Jedro::Epanechnikovo => {
+                if u.abs() >= SQRT_5 {
+                    0.0
+                } else {
+                    0.75 / SQRT_5 * (1.0 - u * u / 5.0)
+                }
+            }
+        }
+    }
+
+    // integral jedra od -inf do u
+    fn integral(&self, u: f64) -> f64 {
+        match self {
+            Jedro::Gaussovo => 0.5 * utils::erfc(-u / SQRT_2),
+            Jedro::Epanechnikovo => {
+                let u = u.clamp(-SQRT_5, SQRT_5);
+                0.5 + 0.75 / SQRT_5 * (u - u * u * u / 15.0)
+            }
+        }
+    }
+
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        match self {
+            Jedro::Gaussovo => utils::probit(g.enakomerno()),
+            // mediana treh enakomernih na [-1, 1] ima Epanechnikovo gostoto
+            Jedro::Epanechnikovo => {
+                let mut u = [0.0; 3].map(|_| 2.0 * g.enakomerno() - 1.0);
+                u.sort_by(|a, b| a.total_cmp(b));
+                SQRT_5 * u[1]
+            }
+        }
+    }
+}
+
+// jedrna ocena gostote f(x) = 1 / (n h) sum K((x - x_i) / h)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
+pub struct JedrnaOcena {
+    // urejeni podatki in h > 0, zasebni, ker cdf in kvantil računata z bisekcijo
+    podatki: Vec<f64>,
+    jedro: Jedro,
+    h: f64,
+}
+
+impl JedrnaOcena {
+    // pravila za širino pasu potrebujejo vsaj dve različni vrednosti, pri konstantnih
+    // podatkih dajo h = 0 in konstruktor se ustavi; takrat podamo SirinaPasu::Fiksna
+    pub fn new(podatki: &[f64], jedro: Jedro, sirina: SirinaPasu) -> Self {
+        assert!(podatki.len() >= 2);
+        assert!(podatki.iter().all(|x| x.is_finite()));
+
+        let mut podatki = podatki.to_vec();
+        podatki.sort_by(|a, b| a.total_cmp(b));
+
+        let h = match sirina {
+            SirinaPasu::Silverman => silverman(&podatki),
+            SirinaPasu::Scott => 1.06 * std_odklon(&podatki) * n_na_minus_petino(&podatki),
+            SirinaPasu::PrecnoPreverjanje => precno_preverjanje(&podatki, jedro),
+            SirinaPasu::Fiksna(h) => h,
+        };
+        assert!(h > 0.0);
+
+        JedrnaOcena { podatki, jedro, h }
+    }
+
+    // naraščajoče urejeni
+    pub fn podatki(&self) -> &[f64] {
+        &self.podatki
+    }
+
+    pub fn jedro(&self) -> Jedro {
+        self.jedro
+    }
+
+    pub fn h(&self) -> f64 {
+        self.h
+    }
+
+    fn n(&self) -> f64 {
+        self.podatki.len() as f64
+    }
+}
+
+// največ toliko točk uporabimo pri prečnem preverjanju, ki stane O(n^2) na poskusno širino
+const MAKS_PRECNO: usize = 500;
+
+fn n_na_minus_petino(podatki: &[f64]) -> f64 {
+    (podatki.len() as f64).powf(-0.2)
+}
+
+fn std_odklon(podatki: &[f64]) -> f64 {
+    let n = podatki.len() as f64;
+    let e = podatki.iter().sum::<f64>() / n;
+    (podatki.iter().map(|x| (x - e).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+}
+
+// podatki so urejeni
+fn silverman(podatki: &[f64]) -> f64 {
+    let mut empiricna = Empiricna::new(podatki);
+    empiricna.interpolacija = Interpolacija::Linearna;
+    let iqr = empiricna.kvantil(0.75) - empiricna.kvantil(0.25);
+    let s = std_odklon(podatki);
+    let razprsenost = if iqr > 0.0 { s.min(iqr / 1.34) } else { s };
+
+    0.9 * razprsenost * n_na_minus_petino(podatki)
+}
+
+fn log_verjetje_brez_ene(podatki: &[f64], jedro: Jedro, h: f64) -> f64 {
+    let n = podatki.len();
+    (0..n)
+        .map(|i| {
+            let gostota = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| jedro.k((podatki[i] - podatki[j]) / h))
+                .sum::<f64>()
+                / ((n - 1) as f64 * h);
+            gostota.max(f64::MIN_POSITIVE).ln()
+        })
+        .sum()
+}
+
+// zlati rez po log h okoli Silvermanove širine, približno 25 poskusnih širin
+fn precno_preverjanje(podatki: &[f64], jedro: Jedro) -> f64 {
+    // pri velikih vzorcih širino izberemo na naključnem podvzorcu s stalnim semenom in jo
+    // prevedemo na cel vzorec s faktorjem (m / n)^(1/5), saj optimalna širina pada kot n^(-1/5)
+    let n = podatki.len();
+    if n > MAKS_PRECNO {
+        let mut g = Generator::new(0);
+        let mut podvzorec = podatki.to_vec();
+        for i in 0..MAKS_PRECNO {
+            let j = i + ((g.enakomerno() * (n - i) as f64) as usize).min(n - i - 1);
+            podvzorec.swap(i, j);
+        }
+        podvzorec.truncate(MAKS_PRECNO);
+        let faktor = (MAKS_PRECNO as f64 / n as f64).powf(0.2);
+        return precno_preverjanje(&podvzorec, jedro) * faktor;
+    }
+
+    let h0 = silverman(podatki);
+    let f = |t: f64| -log_verjetje_brez_ene(podatki, jedro, h0 * t.exp());
+    let zlati = 0.5 * (5f64.sqrt() - 1.0);
+
+    let (mut a, mut b) = (-3.0f64, 2.0f64);
+    let mut c = b - zlati * (b - a);
+    let mut d = a + zlati * (b - a);
+    let (mut fc, mut fd) = (f(c), f(d));
+    while b - a > 1e-4 {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - zlati * (b - a);
+            fc = f(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + zlati * (b - a);
+            fd = f(d);
+        }
+    }
+    h0 * (0.5 * (a + b)).exp()
+}
+
+impl fmt::Display for JedrnaOcena {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "JedrnaOcena(n={}, jedro={:?}, h={})",
+            self.podatki.len(),
+            self.jedro,
+            self.h
+        )
+    }
+}
+
+impl Porazdelitev for JedrnaOcena {
+    fn cdf(&self, x: f64) -> f64 {
+        self.podatki
+            .iter()
+            .map(|xi| self.jedro.integral((x - xi) / self.h))
+            .sum::<f64>()
+            / self.n()
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.podatki
+            .iter()
+            .map(|xi| self.jedro.integral((xi - x) / self.h))
+            .sum::<f64>()
+            / self.n()
+    }
+}
+
+impl Zvezna for JedrnaOcena {
+    fn pdf(&self, x: f64) -> f64 {
+        self.podatki
+            .iter()
+            .map(|xi| self.jedro.k((x - xi) / self.h))
+            .sum::<f64>()
+            / (self.n() * self.h)
+    }
+}
+
+impl PricakovanaVrednost for JedrnaOcena {
+    fn e(&self) -> f64 {
+        self.podatki.iter().sum::<f64>() / self.n()
+    }
+}
+
+// varianca podatkov (deljena z n) plus varianca jedra h^2
+impl Varianca for JedrnaOcena {
+    fn var(&self) -> f64 {
+        let e = self.e();
+        self.podatki.iter().map(|x| (x - e).powi(2)).sum::<f64>() / self.n() + self.h * self.h
+    }
+}
+
+impl Kvantil for JedrnaOcena {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        let lo = self.podatki[0] - 40.0 * self.h;
+        let hi = self.podatki[self.podatki.len() - 1] + 40.0 * self.h;
+        match p {
+            0.0 if self.jedro == Jedro::Gaussovo => f64::NEG_INFINITY,
+            1.0 if self.jedro == Jedro::Gaussovo => f64::INFINITY,
+            _ => utils::bisekcija(|x| self.cdf(x) - p, lo, hi),
+        }
+    }
+}
+
+// izberemo točko vzorca in ji prištejemo šum iz jedra
+impl Vzorcenje for JedrnaOcena {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        let i = ((g.enakomerno() * self.n()) as usize).min(self.podatki.len() - 1);
+        self.podatki[i] + self.h * self.jedro.vzorec(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jedri() {
+        for jedro in [Jedro::Gaussovo, Jedro::Epanechnikovo] {
+            let (a, b) = (f64::NEG_INFINITY, f64::INFINITY);
+            assert!((utils::integriraj(|u| jedro.k(u), a, b) - 1.0).abs() < 1e-9);
+            assert!((utils::integriraj(|u| u * u * jedro.k(u), a, b) - 1.0).abs() < 1e-9);
+            assert!((jedro.integral(0.0) - 0.5).abs() < 1e-15);
+            let u = 0.7;
+            assert!((jedro.integral(u) - utils::integriraj(|v| jedro.k(v), a, u)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_sirina_pasu() {
+        let podatki = [10.0, 2.0, 1.0, 4.0, 3.0];
+        // s = sqrt(12.5), IQR = 4 - 2 (tip 7), n^(-1/5) = 5^(-1/5)
+        let (s, iqr, n) = (12.5f64.sqrt(), 2.0, 5f64.powf(-0.2));
+        let silverman = JedrnaOcena::new(&podatki, Jedro::Gaussovo, SirinaPasu::Silverman);
+        assert!((silverman.h - 0.9 * (iqr / 1.34) * n).abs() < 1e-12);
+        let scott = JedrnaOcena::new(&podatki, Jedro::Gaussovo, SirinaPasu::Scott);
+        assert!((scott.h - 1.06 * s * n).abs() < 1e-12);
+        assert_eq!(
+            JedrnaOcena::new(&podatki, Jedro::Gaussovo, SirinaPasu::Fiksna(0.5)).h,
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_precno_preverjanje() {
+        let vzorec = Normalna::new(0.0, 1.0).vzorci(&mut Generator::new(2), 200);
+        let h = JedrnaOcena::new(&vzorec, Jedro::Gaussovo, SirinaPasu::PrecnoPreverjanje).h;
+        let mut urejen = vzorec.clone();
+        urejen.sort_by(|a, b| a.total_cmp(b));
+        // h je maksimum verjetja z izpustitvijo ene točke
+        let l = |h: f64| log_verjetje_brez_ene(&urejen, Jedro::Gaussovo, h);
+        assert!(l(h) >= l(0.9 * h) && l(h) >= l(1.1 * h));
+        assert!(h > 0.1 && h < 1.0);
+    }
+
+    #[test]
+    fn test_precno_preverjanje_podvzorec() {
+        let vzorec = Normalna::new(0.0, 1.0).vzorci(&mut Generator::new(3), 2000);
+        let d = JedrnaOcena::new(&vzorec, Jedro::Gaussovo, SirinaPasu::PrecnoPreverjanje);
+        assert_eq!(
+            d.h(),
+            JedrnaOcena::new(&vzorec, Jedro::Gaussovo, SirinaPasu::PrecnoPreverjanje).h()
+        );
+        // optimalna širina za N(0, 1) je 1.06 n^(-1/5) = 0.23
+        assert!(d.h() > 0.15 && d.h() < 0.35);
+        assert_eq!(d.podatki().len(), 2000);
+        assert!(d.podatki().windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_cdf_kvantil() {
+        let podatki = [0.0, 1.0, 1.5, 4.0];
+        for jedro in [Jedro::Gaussovo, Jedro::Epanechnikovo] {
+            let d = JedrnaOcena::new(&podatki, jedro, SirinaPasu::Fiksna(0.5));
+            for p in [0.01, 0.25, 0.5, 0.9, 0.999] {
+                assert!((d.cdf(d.kvantil(p)) - p).abs() < 1e-9);
+            }
+            assert!((d.cdf(1.2) + d.sf(1.2) - 1.0).abs() < 1e-12);
+            let ploscina = utils::integriraj(|x| d.pdf(x), 0.5, 2.0);
+            assert!((ploscina - (d.cdf(2.0) - d.cdf(0.5))).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_vzorcenje() {
+        let d = JedrnaOcena::new(
+            &[0.0, 1.0, 5.0],
+            Jedro::Epanechnikovo,
+            SirinaPasu::Fiksna(1.0),
+        );
+        let vzorec = d.vzorci(&mut Generator::new(4), 20000);
+        assert_eq!(vzorec, d.vzorci(&mut Generator::new(4), 20000));
+        let e = vzorec.iter().sum::<f64>() / 20000.0;
+        let var = vzorec.iter().map(|x| (x - e).powi(2)).sum::<f64>() / 20000.0;
+        assert!((e - d.e()).abs() < 0.1);
+        assert!((var / d.var() - 1.0).abs() < 0.05);
+        let najvec = 5.0 + SQRT_5;
+        assert!(vzorec.iter().all(|&x| x > -SQRT_5 && x < najvec));
+    }
+}
@@ -0,0 +1,109 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
+use crate::porazdelitev::*;
+use crate::utils;
+
+// diskretna porazdelitev na {0, 1, ..., n - 1}, podana s tabelo verjetnosti
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
+pub struct KoncnaDiskretna {
+    pub verjetnosti: Vec<f64>,
+}
+
+impl KoncnaDiskretna {
+    // verjetnosti normiramo, da se seštejejo v 1
+    pub fn new(verjetnosti: Vec<f64>) -> Self {
+        assert!(verjetnosti.iter().all(|&p| p >= 0.0));
+        let vsota: f64 = verjetnosti.iter().sum();
+        assert!(vsota > 0.0);
+
+        KoncnaDiskretna {
+            verjetnosti: verjetnosti.into_iter().map(|p| p / vsota).collect(),
+        }
+    }
+
+    // pmf porazdelitve do točke, nad katero je ostala le še zanemarljiva verjetnost
+    pub fn iz_porazdelitve<D: Diskretna + Kvantil>(d: &D) -> Self {
+        let zgornja = d.kvantil(1.0 - 1e-15).min(1e7) as u64;
+        KoncnaDiskretna::new((0..=zgornja).map(|k| d.pmf(k)).collect())
+    }
+}
+
+impl fmt::Display for KoncnaDiskretna {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KoncnaDiskretna(")?;
+        for (k, p) in self.verjetnosti.iter().enumerate() {
+            if k > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", p)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Porazdelitev for KoncnaDiskretna {
+    fn cdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        let k = (x.floor() as usize).min(self.verjetnosti.len() - 1);
+
+        self.verjetnosti[..=k].iter().sum::<f64>().min(1.0)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 1.0;
+        }
+        let k = x.floor() as usize;
+        if k + 1 >= self.verjetnosti.len() {
+            return 0.0;
+        }
+
+        self.verjetnosti[k + 1..].iter().sum::<f64>().min(1.0)
+    }
+}
+
+impl Diskretna for KoncnaDiskretna {
+    fn pmf(&self, x: u64) -> f64 {
+        self.verjetnosti.get(x as usize).copied().unwrap_or(0.0)
+    }
+}
+
+impl PricakovanaVrednost for KoncnaDiskretna {
+    fn e(&self) -> f64 {
+        self.verjetnosti
+            .iter()
+            .enumerate()
+            .map(|(k, p)| k as f64 * p)
+            .sum()
+    }
+}
+
+impl Varianca for KoncnaDiskretna {
+    fn var(&self) -> f64 {
+        let e = self.e();
+        self.verjetnosti
+            .iter()
+            .enumerate()
+            .map(|(k, p)| (k as f64 - e).powi(2) * p)
+            .sum()
+    }
+}
+
+impl Kvantil for KoncnaDiskretna {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        let zadnji = self.verjetnosti.len() as u64 - 1;
+        utils::kvantil_diskretne(|k| self.pmf(k), p, 0, zadnji) as f64
+    }
+}
+
+impl Vzorcenje for KoncnaDiskretna {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
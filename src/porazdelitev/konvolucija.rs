@@ -0,0 +1,247 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
+use crate::porazdelitev::*;
+use crate::utils;
+
+// porazdelitev vsote neodvisnih diskretnih X in Y (pmf_X * pmf_Y)
+pub fn konvolucija<A, B>(x: &A, y: &B) -> KoncnaDiskretna
+where
+    A: Diskretna + Kvantil,
+    B: Diskretna + Kvantil,
+{
+    let x = KoncnaDiskretna::iz_porazdelitve(x);
+    let y = KoncnaDiskretna::iz_porazdelitve(y);
+    let vsota = utils::konvolucija(&x.verjetnosti, &y.verjetnosti);
+
+    // FFT lahko vrne majhne negativne ostanke
+    KoncnaDiskretna::new(vsota.into_iter().map(|p| p.max(0.0)).collect())
+}
+
+// vsota n neodvisnih kopij X, z zaporednim kvadriranjem
+pub fn konvolucijska_potenca<D: Diskretna + Kvantil>(x: &D, n: u64) -> KoncnaDiskretna {
+    assert!(n >= 1);
+    let mut rezultat: Option<KoncnaDiskretna> = None;
+    let mut potenca = KoncnaDiskretna::iz_porazdelitve(x);
+    let mut n = n;
+    loop {
+        if n & 1 == 1 {
+            rezultat = Some(match rezultat {
+                None => KoncnaDiskretna::new(potenca.verjetnosti.clone()),
+                Some(r) => konvolucija(&r, &potenca),
+            });
+        }
+        n >>= 1;
+        if n == 0 {
+            return rezultat.unwrap();
+        }
+        potenca = konvolucija(&potenca, &potenca);
+    }
+}
+
+impl Poisson {
+    // Poisson(λ) + Poisson(μ) = Poisson(λ + μ)
+    pub fn vsota(&self, other: &Poisson) -> Poisson {
+        Poisson::new(self.lambda + other.lambda)
+    }
+}
+
+impl Normalna {
+    // N(μ1, σ1²) + N(μ2, σ2²) = N(μ1 + μ2, σ1² + σ2²)
+    pub fn vsota(&self, other: &Normalna) -> Normalna {
+        Normalna::new(self.mu + other.mu, self.sigma2 + other.sigma2)
+    }
+}
+
+impl Binomska {
+    // Bin(n, p) + Bin(m, p) = Bin(n + m, p), pri različnih p zaprte oblike ni
+    pub fn vsota(&self, other: &Binomska) -> Option<Binomska> {
+        (self.p == other.p).then(|| Binomska::new(self.n + other.n, self.p))
+    }
+}
+
+impl Bernoulli {
+    // Ber(p) + Ber(p) = Bin(2, p), pri različnih p zaprte oblike ni
+    pub fn vsota(&self, other: &Bernoulli) -> Option<Binomska> {
+        (self.p == other.p).then(|| Binomska::new(2, self.p))
+    }
+
+    // vsota n neodvisnih kopij Ber(p) je Bin(n, p)
+    pub fn vsota_kopij(&self, n: u64) -> Binomska {
+        Binomska::new(n, self.p)
+    }
+}
+
+// vsota neodvisnih zveznih X in Y, gostoto računamo numerično
+#[derive(Debug, Clone)]
+pub struct Vsota<A, B> {
+    pub x: A,
+    pub y: B,
+}
+
+const RAZDELITEV: [f64; 9] = [0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999];
+
+impl<A: Zvezna + Kvantil, B: Zvezna + Kvantil> Vsota<A, B> {
+    pub fn new(x: A, y: B) -> Self {
+        Vsota { x, y }
+    }
+
+    // int f_X(t) g(z - t) dt, razdeljen na kvantilih X in z - kvantilih Y
+    fn integral<G>(&self, z: f64, g: G) -> f64
+    where
+        G: Fn(f64) -> f64,
+    {
+        let mut tocke: Vec<f64> = RAZDELITEV
+            .iter()
+            .flat_map(|&p| [self.x.kvantil(p), z - self.y.kvantil(p)])
+            .filter(|t| t.is_finite())
+            .collect();
+        tocke.sort_by(|a, b| a.total_cmp(b));
+        tocke.dedup();
+        tocke.insert(0, f64::NEG_INFINITY);
+        tocke.push(f64::INFINITY);
+
+        tocke
+            .windows(2)
+            .map(|t| utils::integriraj(|s| self.x.pdf(s) * g(z - s), t[0], t[1]))
+            .sum()
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for Vsota<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Vsota({}, {})", self.x, self.y)
+    }
+}
+
+impl<A: Zvezna + Kvantil, B: Zvezna + Kvantil> Porazdelitev for Vsota<A, B> {
+    fn cdf(&self, x: f64) -> f64 {
+        self.integral(x, |t| self.y.cdf(t)).clamp(0.0, 1.0)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.integral(x, |t| self.y.sf(t)).clamp(0.0, 1.0)
+    }
+}
+
+impl<A: Zvezna + Kvantil, B: Zvezna + Kvantil> Zvezna for Vsota<A, B> {
+    fn pdf(&self, x: f64) -> f64 {
+        self.integral(x, |t| self.y.pdf(t)).max(0.0)
+    }
+}
+
+impl<A, B> PricakovanaVrednost for Vsota<A, B>
+where
+    A: Zvezna + Kvantil + PricakovanaVrednost,
+    B: Zvezna + Kvantil + PricakovanaVrednost,
+{
+    fn e(&self) -> f64 {
+        self.x.e() + self.y.e()
+    }
+}
+
+impl<A, B> Varianca for Vsota<A, B>
+where
+    A: Zvezna + Kvantil + Varianca,
+    B: Zvezna + Kvantil + Varianca,
+{
+    fn var(&self) -> f64 {
+        self.x.var() + self.y.var()
+    }
+}
+
+impl<A: Zvezna + Kvantil, B: Zvezna + Kvantil> Kvantil for Vsota<A, B> {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        if p == 0.0 || p == 1.0 {
+            return self.x.kvantil(p) + self.y.kvantil(p);
+        }
+        // začnemo pri vsoti kvantilov in interval širimo, dokler ne objame iskanega kvantila
+        let sredina = self.x.kvantil(p) + self.y.kvantil(p);
+        let mut korak = (self.x.kvantil(0.75) - self.x.kvantil(0.25))
+            + (self.y.kvantil(0.75) - self.y.kvantil(0.25));
+        let (mut lo, mut hi) = (sredina - korak, sredina + korak);
+        while self.cdf(lo) > p {
+            korak *= 2.0;
+            lo -= korak;
+        }
+        while self.cdf(hi) < p {
+            korak *= 2.0;
+            hi += korak;
+        }
+        utils::bisekcija(|x| self.cdf(x) - p, lo, hi)
+    }
+}
+
+impl<A, B> Vzorcenje for Vsota<A, B>
+where
+    A: Zvezna + Kvantil + Vzorcenje,
+    B: Zvezna + Kvantil + Vzorcenje,
+{
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.x.vzorec(g) + self.y.vzorec(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tri_kocke() {
+        let mut kocka = vec![1.0 / 6.0; 7];
+        kocka[0] = 0.0;
+        let kocka = KoncnaDiskretna::new(kocka);
+        let vsota = konvolucijska_potenca(&kocka, 3);
+        let nacini = [1, 3, 6, 10, 15, 21, 25, 27, 27, 25, 21, 15, 10, 6, 3, 1];
+        for k in 0..=20 {
+            let pricakovana = if (3..=18).contains(&k) {
+                nacini[k as usize - 3] as f64 / 216.0
+            } else {
+                0.0
+            };
+            assert!((vsota.pmf(k) - pricakovana).abs() < 1e-12);
+        }
+        let zaporedna = konvolucija(&kocka, &konvolucija(&kocka, &kocka));
+        assert!((0..=18).all(|k| (zaporedna.pmf(k) - vsota.pmf(k)).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_zaprte_oblike() {
+        let (p, q) = (Poisson::new(2.0), Poisson::new(3.5));
+        let numericna = konvolucija(&p, &q);
+        let tocna = p.vsota(&q);
+        assert!((0..40).all(|k| (numericna.pmf(k) - tocna.pmf(k)).abs() < 1e-12));
+
+        let b = Binomska::new(10, 0.3)
+            .vsota(&Binomska::new(5, 0.3))
+            .unwrap();
+        assert_eq!((b.n, b.p), (15, 0.3));
+        assert!(
+            Binomska::new(10, 0.3)
+                .vsota(&Binomska::new(5, 0.4))
+                .is_none()
+        );
+        assert!(Bernoulli::new(0.3).vsota(&Bernoulli::new(0.5)).is_none());
+        let potenca = konvolucijska_potenca(&Bernoulli::new(0.3), 10);
+        let binomska = Bernoulli::new(0.3).vsota_kopij(10);
+        assert!((0..=10).all(|k| (potenca.pmf(k) - binomska.pmf(k)).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_vsota_normalnih() {
+        let (x, y) = (Normalna::new(1.0, 4.0), Normalna::new(-2.0, 5.0));
+        let tocna = x.vsota(&y);
+        assert_eq!((tocna.mu, tocna.sigma2), (-1.0, 9.0));
+        let vsota = Vsota::new(x, y);
+        assert_eq!((vsota.e(), vsota.var()), (-1.0, 9.0));
+        for z in [-8.0, -2.5, -1.0, 0.3, 4.0] {
+            assert!((vsota.pdf(z) - tocna.pdf(z)).abs() < 1e-9);
+            assert!((vsota.cdf(z) - tocna.cdf(z)).abs() < 1e-9);
+            assert!((vsota.sf(z) - tocna.sf(z)).abs() < 1e-9);
+        }
+        for p in [0.01, 0.3, 0.5, 0.95] {
+            assert!((vsota.kvantil(p) - tocna.kvantil(p)).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
+use crate::porazdelitev::*;
+use crate::utils;
+
+// vse, kar mešanica potrebuje od posamezne komponente
+pub trait Komponenta: PricakovanaVrednost + Varianca + Kvantil + Vzorcenje + fmt::Display {}
+
+impl<T: PricakovanaVrednost + Varianca + Kvantil + Vzorcenje + fmt::Display> Komponenta for T {}
+
+pub trait ZveznaKomponenta: Zvezna + Komponenta {}
+
+impl<T: Zvezna + Komponenta> ZveznaKomponenta for T {}
+
+pub trait DiskretnaKomponenta: Diskretna + Komponenta {}
+
+impl<T: Diskretna + Komponenta> DiskretnaKomponenta for T {}
+
+// končna mešanica sum_i w_i F_i, K je dyn ZveznaKomponenta ali dyn DiskretnaKomponenta;
+// zaradi dyn komponent nima serde zapisa (glej serializacija.rs)
+pub struct Mesanica<K: ?Sized> {
+    pub utezi: Vec<f64>,
+    pub komponente: Vec<Box<K>>,
+}
+
+impl<K: Komponenta + ?Sized> Mesanica<K> {
+    // uteži normiramo, da se seštejejo v 1
+    pub fn new(komponente: Vec<(f64, Box<K>)>) -> Self {
+        assert!(!komponente.is_empty());
+        assert!(komponente.iter().all(|(w, _)| *w >= 0.0));
+        let vsota: f64 = komponente.iter().map(|(w, _)| w).sum();
+        assert!(vsota > 0.0);
+
+        let (utezi, komponente) = komponente.into_iter().map(|(w, k)| (w / vsota, k)).unzip();
+        Mesanica { utezi, komponente }
+    }
+
+    fn utezena_vsota<F>(&self, f: F) -> f64
+    where
+        F: Fn(&K) -> f64,
+    {
+        self.utezi
+            .iter()
+            .zip(&self.komponente)
+            .map(|(w, k)| w * f(k))
+            .sum()
+    }
+
+    // najmanjši in največji kvantil komponent omejujeta kvantil mešanice
+    fn meje_kvantila(&self, p: f64) -> (f64, f64) {
+        self.komponente
+            .iter()
+            .map(|k| k.kvantil(p))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| {
+                (lo.min(x), hi.max(x))
+            })
+    }
+}
+
+// uteži pred komponentami, npr. "Mesanica(0.3 Normalna(mu=0, sigma2=1), 0.7 ...)"
+impl<K: Komponenta + ?Sized> fmt::Display for Mesanica<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mesanica(")?;
+        for (i, (w, k)) in self.utezi.iter().zip(&self.komponente).enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", w, k)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<K: Komponenta + ?Sized> Porazdelitev for Mesanica<K> {
+    fn cdf(&self, x: f64) -> f64 {
+        self.utezena_vsota(|k| k.cdf(x))
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.utezena_vsota(|k| k.sf(x))
+    }
+}
+
+impl Zvezna for Mesanica<dyn ZveznaKomponenta> {
+    fn pdf(&self, x: f64) -> f64 {
+        self.utezena_vsota(|k| k.pdf(x))
+    }
+}
+
+impl Diskretna for Mesanica<dyn DiskretnaKomponenta> {
+    fn pmf(&self, x: u64) -> f64 {
+        self.utezena_vsota(|k| k.pmf(x))
+    }
+}
+
+impl<K: Komponenta + ?Sized> PricakovanaVrednost for Mesanica<K> {
+    fn e(&self) -> f64 {
+        self.utezena_vsota(|k| k.e())
+    }
+}
+
+// zakon o popolni varianci: Var X = E[Var(X | K)] + Var(E[X | K])
+impl<K: Komponenta + ?Sized> Varianca for Mesanica<K> {
+    fn var(&self) -> f64 {
+        let e = self.e();
+        self.utezena_vsota(|k| k.var() + (k.e() - e).powi(2))
+    }
+}
+
+impl Kvantil for Mesanica<dyn ZveznaKomponenta> {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        let (lo, hi) = self.meje_kvantila(p);
+        if p == 0.0 {
+            return lo;
+        } else if p == 1.0 {
+            return hi;
+        }
+        utils::bisekcija(|x| self.cdf(x) - p, lo, hi)
+    }
+}
+
+impl Kvantil for Mesanica<dyn DiskretnaKomponenta> {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        let (mut lo, mut hi) = self.meje_kvantila(p);
+        if !hi.is_finite() {
+            return hi;
+        }
+        // iščemo najmanjši k v [lo, hi] s cdf(k) >= p
+        while lo < hi {
+            let k = (0.5 * (lo + hi)).floor();
+            if self.cdf(k) >= p {
+                hi = k;
+            } else {
+                lo = k + 1.0;
+            }
+        }
+        lo
+    }
+}
+
+// najprej izberemo komponento, nato vzorčimo iz nje
+impl<K: Komponenta + ?Sized> Vzorcenje for Mesanica<K> {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        let mut u = g.enakomerno();
+        for (w, k) in self.utezi.iter().zip(&self.komponente) {
+            if u < *w {
+                return k.vzorec(g);
+            }
+            u -= w;
+        }
+        self.komponente.last().unwrap().vzorec(g)
+    }
+}
+
+const EM_MAX_ITERACIJ: usize = 1000;
+const EM_TOLERANCA: f64 = 1e-10;
+
+// algoritem EM; `gostota(j, x)` je gostota j-te komponente pri trenutnih parametrih,
+// `m_korak(odgovornosti)` iz odgovornosti posodobi parametre in vrne nove uteži
+fn em<G, M>(podatki: &[f64], k: usize, gostota: G, mut m_korak: M) -> Vec<f64>
+where
+    G: Fn(usize, f64) -> f64,
+    M: FnMut(&[Vec<f64>]) -> Vec<f64>,
+{
+    let mut utezi = vec![1.0 / k as f64; k];
+    let mut odgovornosti = vec![vec![0.0; podatki.len()]; k];
+    let mut prejsnja = f64::NEG_INFINITY;
+
+    for _ in 0..EM_MAX_ITERACIJ {
+        // korak E
+        let mut log_verjetje = 0.0;
+        for (i, &x) in podatki.iter().enumerate() {
+            let gostote: Vec<f64> = (0..k).map(|j| utezi[j] * gostota(j, x)).collect();
+            let vsota: f64 = gostote.iter().sum();
+            for j in 0..k {
+                odgovornosti[j][i] = if vsota > 0.0 {
+                    gostote[j] / vsota
+                } else {
+                    1.0 / k as f64
+                };
+            }
+            log_verjetje += vsota.ln();
+        }
+
+        // korak M
+        utezi = m_korak(&odgovornosti);
+
+        if (log_verjetje - prejsnja).abs() < EM_TOLERANCA * log_verjetje.abs().max(1.0) {
+            break;
+        }
+        prejsnja = log_verjetje;
+    }
+    utezi
+}
+
+// začetne vrednosti: povprečja k zaporednih skupin urejenih podatkov
+fn zacetna_povprecja(podatki: &[f64], k: usize) -> Vec<f64> {
+    let mut urejeni = podatki.to_vec();
+    urejeni.sort_by(|a, b| a.total_cmp(b));
+    (0..k)
+        .map(|j| {
+            let skupina = &urejeni[j * urejeni.len() / k..(j + 1) * urejeni.len() / k];
+            skupina.iter().sum::<f64>() / skupina.len() as f64
+        })
+        .collect()
+}
+
+impl Mesanica<dyn ZveznaKomponenta> {
+    // mešanica k normalnih porazdelitev po metodi največjega verjetja (EM)
+    pub fn prilagodi_normalne(podatki: &[f64], k: usize) -> Self {
+        assert!(k >= 1 && podatki.len() >= 2 * k);
+        let n = podatki.len() as f64;
+        let povprecje = podatki.iter().sum::<f64>() / n;
+        let varianca = podatki.iter().map(|x| (x - povprecje).powi(2)).sum::<f64>() / n;
+        // spodnja meja varianc, da komponenta ne skolapsira na eno točko
+        let min_var = 1e-6 * varianca.max(f64::MIN_POSITIVE);
+
+        let mu = core::cell::RefCell::new(zacetna_povprecja(podatki, k));
+        let sigma2 = core::cell::RefCell::new(vec![varianca.max(min_var); k]);
+
+        let utezi = em(
+            podatki,
+            k,
+            |j, x| Normalna::new(mu.borrow()[j], sigma2.borrow()[j]).pdf(x),
+            |odgovornosti| {
+                let mut mu = mu.borrow_mut();
+                let mut sigma2 = sigma2.borrow_mut();
+                odgovornosti
+                    .iter()
+                    .enumerate()
+                    .map(|(j, r)| {
+                        let nj: f64 = r.iter().sum();
+                        if nj > 0.0 {
+                            mu[j] = r.iter().zip(podatki).map(|(r, x)| r * x).sum::<f64>() / nj;
+                            sigma2[j] = (r
+                                .iter()
+                                .zip(podatki)
+                                .map(|(r, x)| r * (x - mu[j]).powi(2))
+                                .sum::<f64>()
+                                / nj)
+                                .max(min_var);
+                        }
+                        nj / n
+                    })
+                    .collect()
+            },
+        );
+
+        let komponente = utezi
+            .into_iter()
+            .zip(mu.into_inner().into_iter().zip(sigma2.into_inner()))
+            .map(|(w, (mu, sigma2))| {
+                (
+                    w,
+                    Box::new(Normalna::new(mu, sigma2)) as Box<dyn ZveznaKomponenta>,
+                )
+            })
+            .collect();
+        Mesanica::new(komponente)
+    }
+}
+
+impl Mesanica<dyn DiskretnaKomponenta> {
+    // mešanica k Poissonovih porazdelitev po metodi največjega verjetja (EM)
+    pub fn prilagodi_poissonove(podatki: &[u64], k: usize) -> Self {
+        assert!(k >= 1 && podatki.len() >= k);
+        let podatki: Vec<f64> = podatki.iter().map(|&x| x as f64).collect();
+        let n = podatki.len() as f64;
+
+        let lambda = core::cell::RefCell::new(zacetna_povprecja(&podatki, k));
+
+        let utezi = em(
+            &podatki,
+            k,
+            |j, x| Poisson::new(lambda.borrow()[j]).pmf(x as u64),
+            |odgovornosti| {
+                let mut lambda = lambda.borrow_mut();
+                odgovornosti
+                    .iter()
+                    .enumerate()
+                    .map(|(j, r)| {
+                        let nj: f64 = r.iter().sum();
+                        if nj > 0.0 {
+                            lambda[j] =
+                                r.iter().zip(&podatki).map(|(r, x)| r * x).sum::<f64>() / nj;
+                        }
+                        nj / n
+                    })
+                    .collect()
+            },
+        );
+
+        let komponente = utezi
+            .into_iter()
+            .zip(lambda.into_inner())
+            .map(|(w, lambda)| {
+                (
+                    w,
+                    Box::new(Poisson::new(lambda)) as Box<dyn DiskretnaKomponenta>,
+                )
+            })
+            .collect();
+        Mesanica::new(komponente)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_momenti_mesanice() {
+        let m: Mesanica<dyn ZveznaKomponenta> = Mesanica::new(vec![
+            (1.0, Box::new(Normalna::new(-2.0, 1.0))),
+            (3.0, Box::new(Eksponentna::new(0.5))),
+        ]);
+        assert_eq!(m.utezi, vec![0.25, 0.75]);
+        assert!((m.e() - 1.0).abs() < 1e-12);
+        // 0.25 * (1 + 9) + 0.75 * (4 + 1)
+        assert!((m.var() - 6.25).abs() < 1e-12);
+        assert!((m.cdf(m.kvantil(0.3)) - 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_em_normalne() {
+        let prava: Mesanica<dyn ZveznaKomponenta> = Mesanica::new(vec![
+            (0.3, Box::new(Normalna::new(-3.0, 1.0))),
+            (0.7, Box::new(Normalna::new(4.0, 2.0))),
+        ]);
+        let mut g = Generator::new(7);
+        let podatki = prava.vzorci(&mut g, 5000);
+
+        let m = Mesanica::prilagodi_normalne(&podatki, 2);
+        assert!((m.utezi[0] - 0.3).abs() < 0.03);
+        assert!((m.komponente[0].e() + 3.0).abs() < 0.1);
+        assert!((m.komponente[1].e() - 4.0).abs() < 0.1);
+        assert!((m.komponente[1].var() - 2.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_em_poissonove() {
+        let prava: Mesanica<dyn DiskretnaKomponenta> = Mesanica::new(vec![
+            (0.4, Box::new(Poisson::new(2.0))),
+            (0.6, Box::new(Poisson::new(10.0))),
+        ]);
+        let mut g = Generator::new(11);
+        let podatki: Vec<u64> = prava
+            .vzorci(&mut g, 5000)
+            .iter()
+            .map(|&x| x as u64)
+            .collect();
+
+        let m = Mesanica::prilagodi_poissonove(&podatki, 2);
+        assert!((m.utezi[0] - 0.4).abs() < 0.03);
+        assert!((m.komponente[0].e() - 2.0).abs() < 0.15);
+        assert!((m.komponente[1].e() - 10.0).abs() < 0.2);
+        assert!((m.utezi.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+}
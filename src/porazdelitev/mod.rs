@@ -1,16 +1,48 @@
+use crate::nakljucno::Generator;
+// brez std podmoduli dobijo Vec, String, ... prek use crate::porazdelitev::*
+#[cfg(not(any(feature = "std", test)))]
+pub(crate) use crate::prelude::*;
+
 pub trait Porazdelitev {
     // P(X <= x) (comulatice distribution function)
     fn cdf(&self, x: f64) -> f64;
+
+    // P(X > x) (survival function), v repu je vredno povoziti z natančnejšo formulo
+    fn sf(&self, x: f64) -> f64 {
+        1.0 - self.cdf(x)
+    }
+
+    // cdf v vseh točkah x naenkrat, rezultat zapiše v izhod
+    fn cdf_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        assert_eq!(x.len(), izhod.len());
+        for (x, y) in x.iter().zip(izhod.iter_mut()) {
+            *y = self.cdf(*x);
+        }
+    }
 }
 
 pub trait Diskretna: Porazdelitev {
     // P(X = x) (probability mass function)
     fn pmf(&self, x: u64) -> f64;
+
+    fn pmf_batch(&self, x: &[u64], izhod: &mut [f64]) {
+        assert_eq!(x.len(), izhod.len());
+        for (x, y) in x.iter().zip(izhod.iter_mut()) {
+            *y = self.pmf(*x);
+        }
+    }
 }
 
 pub trait Zvezna: Porazdelitev {
     // f_X (x) (probability density function)
     fn pdf(&self, x: f64) -> f64;
+
+    fn pdf_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        assert_eq!(x.len(), izhod.len());
+        for (x, y) in x.iter().zip(izhod.iter_mut()) {
+            *y = self.pdf(*x);
+        }
+    }
 }
 
 pub trait PricakovanaVrednost: Porazdelitev {
@@ -21,20 +53,83 @@ pub trait Varianca: Porazdelitev {
     fn var(&self) -> f64;
 }
 
+pub trait Kvantil: Porazdelitev {
+    // najmanjši x, za katerega je P(X <= x) >= p
+    fn kvantil(&self, p: f64) -> f64;
+
+    // x, za katerega je P(X > x) = q (inverse survival function)
+    fn isf(&self, q: f64) -> f64 {
+        self.kvantil(1.0 - q)
+    }
+}
+
+pub trait Vzorcenje: Porazdelitev {
+    fn vzorec(&self, g: &mut Generator) -> f64;
+
+    fn vzorci(&self, g: &mut Generator, n: usize) -> Vec<f64> {
+        (0..n).map(|_| self.vzorec(g)).collect()
+    }
+}
+
 mod bernoulli;
 mod binomska;
+mod dinamicna;
 mod eksponentna;
+mod empiricna;
 mod enakomerna_interval;
 mod geometrijska;
+mod hi_kvadrat;
 mod hipergeometrijska;
+mod interval;
+mod jedrna_ocena;
+mod koncna_diskretna;
+mod konvolucija;
+mod mesanica;
 mod normalna;
+mod okrnjena;
 mod poisson;
+mod priblizki;
+mod prileganje;
+mod razdalje;
+pub mod register;
+#[cfg(feature = "serde")]
+mod serializacija;
+mod studentova_t;
+mod transformirana;
+mod verjetnostni_grafi;
+#[cfg(feature = "parallel")]
+mod vzporedno;
+mod zapis;
 
 pub use self::bernoulli::Bernoulli;
 pub use self::binomska::Binomska;
+pub use self::dinamicna::DynPorazdelitev;
 pub use self::eksponentna::Eksponentna;
+pub use self::empiricna::{Empiricna, Interpolacija};
 pub use self::enakomerna_interval::EnakomernaInterval;
 pub use self::geometrijska::Geometrijska;
+pub use self::hi_kvadrat::HiKvadrat;
 pub use self::hipergeometrijska::Hipergeometrijska;
+pub use self::interval::{
+    VerjetnostIntervala, Vkljucenost, verjetnost_diskretne, verjetnost_zvezne,
+};
+pub use self::jedrna_ocena::{JedrnaOcena, Jedro, SirinaPasu};
+pub use self::koncna_diskretna::KoncnaDiskretna;
+pub use self::konvolucija::{Vsota, konvolucija, konvolucijska_potenca};
+pub use self::mesanica::{DiskretnaKomponenta, Komponenta, Mesanica, ZveznaKomponenta};
 pub use self::normalna::Normalna;
+pub use self::okrnjena::{Okrnjena, Okrnljiva, momenti_diskretne, momenti_zvezne};
 pub use self::poisson::Poisson;
+pub use self::priblizki::{Diskretizirana, NapakaPriblizka, napaka_priblizka};
+pub use self::prileganje::{Kriterij, Prileganje, log_verjetje, najboljse_prileganje, prilagodi};
+pub use self::razdalje::{
+    hellingerjeva_razdalja, kolmogorova_razdalja, razdalja_totalne_variacije,
+};
+#[cfg(feature = "serde")]
+pub(crate) use self::serializacija::Serializirana;
+pub use self::studentova_t::StudentovaT;
+pub use self::transformirana::{Preslikava, Transformirana};
+pub use self::verjetnostni_grafi::{Polozaji, ks_pas, polozaji, pp_tocke, qq_pas, qq_tocke};
+#[cfg(feature = "parallel")]
+pub use self::vzporedno::{cdf_vzporedno, pdf_vzporedno, pmf_vzporedno};
+pub use self::zapis::{NapakaRazclenjevanja, razcleni};
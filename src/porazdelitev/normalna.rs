@@ -1,7 +1,12 @@
+use crate::nakljucno::Generator;
 use crate::porazdelitev::*;
 use crate::utils;
-use std::f64::consts::{PI, SQRT_2};
+use core::f64::consts::{PI, SQRT_2};
+use core::fmt;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
 pub struct Normalna {
     pub mu: f64,
     pub sigma2: f64,
@@ -14,6 +19,12 @@ impl Normalna {
     }
 }
 
+impl fmt::Display for Normalna {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Normalna(mu={}, sigma2={})", self.mu, self.sigma2)
+    }
+}
+
 impl Porazdelitev for Normalna {
     fn cdf(&self, x: f64) -> f64 {
         let sigma = self.sigma2.sqrt();
@@ -21,6 +32,21 @@ impl Porazdelitev for Normalna {
 
         0.5 * (1.0 + utils::erf(transf))
     }
+
+    fn sf(&self, x: f64) -> f64 {
+        let sigma = self.sigma2.sqrt();
+        let transf = (x - self.mu) / (sigma * SQRT_2);
+
+        0.5 * utils::erfc(transf)
+    }
+
+    fn cdf_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        assert_eq!(x.len(), izhod.len());
+        let faktor = 1.0 / (self.sigma2.sqrt() * SQRT_2);
+        for (x, y) in x.iter().zip(izhod.iter_mut()) {
+            *y = 0.5 * (1.0 + utils::erf((x - self.mu) * faktor));
+        }
+    }
 }
 
 impl Zvezna for Normalna {
@@ -29,6 +55,16 @@ impl Zvezna for Normalna {
         let exponent = -((x - self.mu).powi(2)) / (2.0 * self.sigma2);
         coef * exponent.exp()
     }
+
+    fn pdf_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        assert_eq!(x.len(), izhod.len());
+        let coef = 1.0 / (2.0 * PI * self.sigma2).sqrt();
+        let a = -1.0 / (2.0 * self.sigma2);
+        for (x, y) in x.iter().zip(izhod.iter_mut()) {
+            let d = x - self.mu;
+            *y = coef * (a * d * d).exp();
+        }
+    }
 }
 
 impl PricakovanaVrednost for Normalna {
@@ -42,3 +78,21 @@ impl Varianca for Normalna {
         self.sigma2
     }
 }
+
+impl Kvantil for Normalna {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        self.mu + self.sigma2.sqrt() * utils::probit(p)
+    }
+
+    fn isf(&self, q: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&q));
+        self.mu - self.sigma2.sqrt() * utils::probit(q)
+    }
+}
+
+impl Vzorcenje for Normalna {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
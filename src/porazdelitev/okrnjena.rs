@@ -0,0 +1,370 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
+use crate::porazdelitev::*;
+use crate::utils;
+use core::f64::consts::{PI, SQRT_2};
+
+// (E, Var) izračunamo ob prvi uporabi, pri zveznih je to numerična integracija; z std je
+// predpomnilnik OnceLock, da ostane Okrnjena Sync, brez std pa OnceCell
+#[cfg(any(feature = "std", test))]
+type Momenti = std::sync::OnceLock<(f64, f64)>;
+#[cfg(not(any(feature = "std", test)))]
+type Momenti = core::cell::OnceCell<(f64, f64)>;
+
+// porazdelitev X pogojno na a < X <= b, meji sta lahko neskončni
+#[derive(Debug, Clone)]
+pub struct Okrnjena<D> {
+    // zasebna polja, ker so masa, rep in momenti izračunani iz njih
+    porazdelitev: D,
+    a: f64,
+    b: f64,
+    // P(a < X <= b)
+    masa: f64,
+    // interval leži v zgornjem repu, zato računamo s sf namesto s cdf
+    rep: bool,
+    momenti: Momenti,
+}
+
+impl<D: Porazdelitev> Okrnjena<D> {
+    pub fn new(porazdelitev: D, a: f64, b: f64) -> Self {
+        assert!(a < b);
+        let (masa, rep) = masa(&porazdelitev, a, b);
+        assert!(masa > 0.0);
+
+        Okrnjena {
+            porazdelitev,
+            a,
+            b,
+            masa,
+            rep,
+            momenti: Momenti::new(),
+        }
+    }
+}
+
+impl<D> Okrnjena<D> {
+    pub fn porazdelitev(&self) -> &D {
+        &self.porazdelitev
+    }
+
+    pub fn a(&self) -> f64 {
+        self.a
+    }
+
+    pub fn b(&self) -> f64 {
+        self.b
+    }
+
+    // P(a < X <= b) za neokrnjeno porazdelitev
+    pub fn masa(&self) -> f64 {
+        self.masa
+    }
+}
+
+// cdf in sf v neskončnih mejah poznamo točno, brez napake zaokroževanja vsot diskretnih
+pub(crate) fn cdf_meja<D: Porazdelitev + ?Sized>(d: &D, x: f64) -> f64 {
+    match x {
+        f64::NEG_INFINITY => 0.0,
+        f64::INFINITY => 1.0,
+        _ => d.cdf(x),
+    }
+}
+
+pub(crate) fn sf_meja<D: Porazdelitev + ?Sized>(d: &D, x: f64) -> f64 {
+    match x {
+        f64::NEG_INFINITY => 1.0,
+        f64::INFINITY => 0.0,
+        _ => d.sf(x),
+    }
+}
+
+pub(crate) fn masa<D: Porazdelitev + ?Sized>(d: &D, a: f64, b: f64) -> (f64, bool) {
+    if cdf_meja(d, a) > 0.5 {
+        (sf_meja(d, a) - sf_meja(d, b), true)
+    } else {
+        (cdf_meja(d, b) - cdf_meja(d, a), false)
+    }
+}
+
+impl<D: fmt::Display> fmt::Display for Okrnjena<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Okrnjena({}, a={}, b={})",
+            self.porazdelitev, self.a, self.b
+        )
+    }
+}
+
+impl<D: Porazdelitev> Porazdelitev for Okrnjena<D> {
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= self.a {
+            return 0.0;
+        } else if x >= self.b {
+            return 1.0;
+        }
+
+        let d = &self.porazdelitev;
+        if self.rep {
+            ((sf_meja(d, self.a) - d.sf(x)) / self.masa).clamp(0.0, 1.0)
+        } else {
+            ((d.cdf(x) - cdf_meja(d, self.a)) / self.masa).clamp(0.0, 1.0)
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x <= self.a {
+            return 1.0;
+        } else if x >= self.b {
+            return 0.0;
+        }
+
+        let d = &self.porazdelitev;
+        if self.rep {
+            ((d.sf(x) - sf_meja(d, self.b)) / self.masa).clamp(0.0, 1.0)
+        } else {
+            ((cdf_meja(d, self.b) - d.cdf(x)) / self.masa).clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl<D: Zvezna> Zvezna for Okrnjena<D> {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < self.a || x > self.b {
+            return 0.0;
+        }
+
+        self.porazdelitev.pdf(x) / self.masa
+    }
+}
+
+impl<D: Diskretna> Diskretna for Okrnjena<D> {
+    fn pmf(&self, x: u64) -> f64 {
+        let x_f = x as f64;
+        if x_f <= self.a || x_f > self.b {
+            return 0.0;
+        }
+
+        self.porazdelitev.pmf(x) / self.masa
+    }
+}
+
+impl<D: Kvantil> Kvantil for Okrnjena<D> {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        let d = &self.porazdelitev;
+        let x = if self.rep {
+            d.isf(sf_meja(d, self.a) - p * self.masa)
+        } else {
+            d.kvantil(cdf_meja(d, self.a) + p * self.masa)
+        };
+        if x > self.a || self.a == f64::NEG_INFINITY {
+            return x.min(self.b);
+        }
+        // a je izključen, pri diskretnih pa je lahko točka nosilca, ki jo vrne d.kvantil(F(a));
+        // prvo točko nad a dobimo z nekoliko večjim p, sicer zaokrožitev vrne spet a
+        let p = p.max(1e-9);
+        let x = if self.rep {
+            d.isf(sf_meja(d, self.a) - p * self.masa)
+        } else {
+            d.kvantil(cdf_meja(d, self.a) + p * self.masa)
+        };
+
+        x.clamp(self.a, self.b)
+    }
+}
+
+// vzorčimo z inverzom, ki v zgornjem repu gre prek isf, zato ne izgubimo natančnosti
+impl<D: Kvantil> Vzorcenje for Okrnjena<D> {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
+
+pub trait Okrnljiva: Porazdelitev {
+    // (E[X | a < X <= b], Var[X | a < X <= b])
+    fn okrnjeni_momenti(&self, a: f64, b: f64) -> (f64, f64);
+}
+
+impl<D: Okrnljiva> Okrnjena<D> {
+    fn momenti(&self) -> (f64, f64) {
+        *self
+            .momenti
+            .get_or_init(|| self.porazdelitev.okrnjeni_momenti(self.a, self.b))
+    }
+}
+
+impl<D: Okrnljiva> PricakovanaVrednost for Okrnjena<D> {
+    fn e(&self) -> f64 {
+        self.momenti().0
+    }
+}
+
+impl<D: Okrnljiva> Varianca for Okrnjena<D> {
+    fn var(&self) -> f64 {
+        self.momenti().1
+    }
+}
+
+// integral razbijemo na kvantilih okrnjene porazdelitve, da ozke vrhove zagotovo zadanemo
+pub fn momenti_zvezne<D: Zvezna + Kvantil>(d: &D, a: f64, b: f64) -> (f64, f64) {
+    let (masa, rep) = masa(d, a, b);
+    let mut tocke = vec![a];
+    for p in [0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999] {
+        let x = if rep {
+            d.isf(sf_meja(d, a) - p * masa)
+        } else {
+            d.kvantil(cdf_meja(d, a) + p * masa)
+        };
+        if x.is_finite() && x > *tocke.last().unwrap() && x < b {
+            tocke.push(x);
+        }
+    }
+    tocke.push(b);
+
+    let integral = |f: &dyn Fn(f64) -> f64| {
+        tocke
+            .windows(2)
+            .map(|t| utils::integriraj(|x| f(x) * d.pdf(x), t[0], t[1]))
+            .sum::<f64>()
+            / masa
+    };
+    let e = integral(&|x| x);
+    let var = integral(&|x| (x - e).powi(2));
+    (e, var)
+}
+
+pub fn momenti_diskretne<D: Diskretna>(d: &D, a: f64, b: f64) -> (f64, f64) {
+    let (masa, _) = masa(d, a, b);
+    let mut k = if a < 0.0 { 0 } else { a.floor() as u64 + 1 };
+    let mut cleni = Vec::new();
+    let mut vsota = 0.0;
+    while (k as f64) <= b && vsota < masa * (1.0 - 1e-15) {
+        let clen = d.pmf(k);
+        if clen.is_nan() || (clen <= 0.0 && vsota > 0.5 * masa) {
+            break;
+        }
+        vsota += clen;
+        cleni.push((k as f64, clen));
+        k += 1;
+    }
+
+    let e = cleni.iter().map(|(x, p)| x * p).sum::<f64>() / vsota;
+    let var = cleni.iter().map(|(x, p)| (x - e).powi(2) * p).sum::<f64>() / vsota;
+    (e, var)
+}
+
+impl Okrnljiva for Normalna {
+    fn okrnjeni_momenti(&self, a: f64, b: f64) -> (f64, f64) {
+        let sigma = self.sigma2.sqrt();
+        let alfa = (a - self.mu) / sigma;
+        let beta = (b - self.mu) / sigma;
+        let phi = |z: f64| (-0.5 * z * z).exp() / (2.0 * PI).sqrt();
+        // z * phi(z) gre proti 0 v neskončnosti
+        let z_phi = |z: f64| if z.is_finite() { z * phi(z) } else { 0.0 };
+
+        let z = if alfa > 0.0 {
+            0.5 * (utils::erfc(alfa / SQRT_2) - utils::erfc(beta / SQRT_2))
+        } else {
+            0.5 * (utils::erfc(-beta / SQRT_2) - utils::erfc(-alfa / SQRT_2))
+        };
+        let delta = (phi(alfa) - phi(beta)) / z;
+
+        let e = self.mu + sigma * delta;
+        let var = self.sigma2 * (1.0 + (z_phi(alfa) - z_phi(beta)) / z - delta * delta);
+        (e, var)
+    }
+}
+
+impl Okrnljiva for Eksponentna {
+    fn okrnjeni_momenti(&self, a: f64, b: f64) -> (f64, f64) {
+        momenti_zvezne(self, a, b)
+    }
+}
+
+impl Okrnljiva for EnakomernaInterval {
+    fn okrnjeni_momenti(&self, a: f64, b: f64) -> (f64, f64) {
+        momenti_zvezne(self, a, b)
+    }
+}
+
+impl Okrnljiva for Bernoulli {
+    fn okrnjeni_momenti(&self, a: f64, b: f64) -> (f64, f64) {
+        momenti_diskretne(self, a, b)
+    }
+}
+
+impl Okrnljiva for Binomska {
+    fn okrnjeni_momenti(&self, a: f64, b: f64) -> (f64, f64) {
+        momenti_diskretne(self, a, b)
+    }
+}
+
+impl Okrnljiva for Geometrijska {
+    fn okrnjeni_momenti(&self, a: f64, b: f64) -> (f64, f64) {
+        momenti_diskretne(self, a, b)
+    }
+}
+
+impl Okrnljiva for Hipergeometrijska {
+    fn okrnjeni_momenti(&self, a: f64, b: f64) -> (f64, f64) {
+        momenti_diskretne(self, a, b)
+    }
+}
+
+impl Okrnljiva for Poisson {
+    fn okrnjeni_momenti(&self, a: f64, b: f64) -> (f64, f64) {
+        momenti_diskretne(self, a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polovicna_normalna() {
+        let d = Okrnjena::new(Normalna::new(0.0, 1.0), 0.0, f64::INFINITY);
+        assert!((d.cdf(1.0) - 0.6826894921370859).abs() < 1e-12);
+        assert!((d.pdf(0.5) - 2.0 * Normalna::new(0.0, 1.0).pdf(0.5)).abs() < 1e-15);
+        assert!((d.e() - (2.0 / PI).sqrt()).abs() < 1e-12);
+        assert!((d.var() - (1.0 - 2.0 / PI)).abs() < 1e-12);
+        // numerični momenti se ujemajo z analitičnimi
+        let (e, var) = momenti_zvezne(d.porazdelitev(), 0.0, f64::INFINITY);
+        assert!((e - d.e()).abs() < 1e-9);
+        assert!((var - d.var()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_poisson_brez_nicle() {
+        let p = Poisson::new(2.0);
+        let d = Okrnjena::new(Poisson::new(2.0), 0.0, f64::INFINITY);
+        assert_eq!(d.pmf(0), 0.0);
+        assert!((d.pmf(1) - p.pmf(1) / (1.0 - p.pmf(0))).abs() < 1e-15);
+        assert!((d.e() - 2.0 / (1.0 - (-2.0f64).exp())).abs() < 1e-12);
+        assert_eq!(d.kvantil(0.3), 1.0);
+        assert_eq!(d.kvantil(0.4), 2.0);
+        // 0 ni v nosilcu
+        assert_eq!(d.kvantil(0.0), 1.0);
+        assert_eq!(Okrnjena::new(Poisson::new(2.0), 0.5, 4.0).kvantil(0.0), 1.0);
+        let d = Okrnjena::new(Poisson::new(2.0), 7.0, f64::INFINITY);
+        assert_eq!(d.kvantil(0.0), 8.0);
+        assert!((Okrnjena::new(Normalna::new(0.0, 1.0), 0.5, 1.0).kvantil(0.0) - 0.5).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_vzorci_v_repu() {
+        let d = Okrnjena::new(Normalna::new(0.0, 1.0), 8.0, f64::INFINITY);
+        let mut g = Generator::new(42);
+        // momenti se izračunajo enkrat in ostanejo tudi v kopiji
+        let (e, var) = (d.e(), d.var());
+        let kopija = d.clone();
+        assert_eq!((kopija.e(), kopija.var()), (e, var));
+        assert!(var > 0.0 && var < 0.02);
+        let vzorci = d.vzorci(&mut g, 1000);
+        assert!(vzorci.iter().all(|&x| x > 8.0 && x.is_finite()));
+        let povprecje = vzorci.iter().sum::<f64>() / 1000.0;
+        assert!((povprecje - d.e()).abs() < 0.05);
+    }
+}
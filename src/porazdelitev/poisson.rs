@@ -1,6 +1,12 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
 use crate::porazdelitev::*;
 use crate::utils;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
 pub struct Poisson {
     pub lambda: f64,
 }
@@ -10,11 +16,41 @@ impl Poisson {
         assert!(lambda >= 0.0);
         Poisson { lambda }
     }
+
+    // točka, nad katero je cdf na dvojno natančnost enaka 1; cdf in cdf_batch seštevata
+    // največ do nje
+    fn meja(&self) -> u64 {
+        (self.lambda + 40.0 * self.lambda.sqrt() + 40.0) as u64
+    }
+}
+
+impl fmt::Display for Poisson {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Poisson(lambda={})", self.lambda)
+    }
 }
 
 impl Diskretna for Poisson {
     fn pmf(&self, x: u64) -> f64 {
-        self.lambda.powi(x as i32) * (-self.lambda).exp() / utils::factorial(x)
+        if self.lambda == 0.0 {
+            return if x == 0 { 1.0 } else { 0.0 };
+        }
+        // v logaritmih, sicer λ^x in x! hitro prekoračita obseg f64
+        (x as f64 * self.lambda.ln() - self.lambda - utils::ln_factorial(x)).exp()
+    }
+
+    fn pmf_batch(&self, x: &[u64], izhod: &mut [f64]) {
+        assert_eq!(x.len(), izhod.len());
+        if self.lambda == 0.0 {
+            for (x, y) in x.iter().zip(izhod.iter_mut()) {
+                *y = if *x == 0 { 1.0 } else { 0.0 };
+            }
+            return;
+        }
+        let ln_lambda = self.lambda.ln();
+        for (x, y) in x.iter().zip(izhod.iter_mut()) {
+            *y = (*x as f64 * ln_lambda - self.lambda - utils::ln_factorial(*x)).exp();
+        }
     }
 }
 
@@ -23,10 +59,16 @@ impl Porazdelitev for Poisson {
         if x < 0.0 {
             return 0.0;
         }
-        let k = x.floor() as u64;
+        let k = (x.floor() as u64).min(self.meja());
 
         utils::sestej(|i: u64| <Poisson as Diskretna>::pmf(self, i), k)
     }
+
+    // tabelo kumulativnih vsot izračunamo enkrat, do največjega x oz. do meje
+    fn cdf_batch(&self, x: &[f64], izhod: &mut [f64]) {
+        let kumulativna = utils::kumulativna_tabela(|i| self.pmf(i), x, self.meja());
+        utils::iz_kumulativne(&kumulativna, x, izhod);
+    }
 }
 
 impl PricakovanaVrednost for Poisson {
@@ -40,3 +82,20 @@ impl Varianca for Poisson {
         self.lambda
     }
 }
+
+impl Kvantil for Poisson {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        if p == 1.0 {
+            return f64::INFINITY;
+        }
+        let od = utils::zacetek_vsote(self.lambda, self.lambda, 0);
+        utils::kvantil_diskretne(|i| self.pmf(i), p, od, u64::MAX) as f64
+    }
+}
+
+impl Vzorcenje for Poisson {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
@@ -0,0 +1,181 @@
+use crate::porazdelitev::*;
+
+impl Binomska {
+    // Bin(n, p) ≈ Poisson(np) za velike n in majhne p
+    pub fn poissonov_priblizek(&self) -> Poisson {
+        Poisson::new(self.n as f64 * self.p)
+    }
+
+    // Bin(n, p) ≈ N(np, np(1 - p)), None pri p = 0 ali p = 1
+    pub fn normalni_priblizek(&self) -> Option<Normalna> {
+        normalni(self)
+    }
+}
+
+impl Poisson {
+    // Poisson(λ) ≈ N(λ, λ) za velike λ, None pri λ = 0
+    pub fn normalni_priblizek(&self) -> Option<Normalna> {
+        normalni(self)
+    }
+}
+
+impl Hipergeometrijska {
+    // vlečenje brez vračanja je pri veliki populaciji skoraj enako vlečenju z vračanjem
+    pub fn binomski_priblizek(&self) -> Binomska {
+        Binomska::new(self.r, self.s as f64 / self.n as f64)
+    }
+
+    // None, če je r ali s enak 0 ali n
+    pub fn normalni_priblizek(&self) -> Option<Normalna> {
+        normalni(self)
+    }
+}
+
+// pri varianci 0 je porazdelitev točkovna in normalnega približka ni
+fn normalni<D: PricakovanaVrednost + Varianca>(d: &D) -> Option<Normalna> {
+    let var = d.var();
+    (var > 0.0).then(|| Normalna::new(d.e(), var))
+}
+
+// zvezna porazdelitev, zaokrožena na {0, 1, 2, ...}: P(X <= k) = F(k + zamik);
+// zamik 1/2 je popravek za zveznost, vsa masa pod 0 pripade točki 0
+#[derive(Debug, Clone)]
+pub struct Diskretizirana<D> {
+    pub porazdelitev: D,
+    pub zamik: f64,
+}
+
+impl<D: Zvezna> Diskretizirana<D> {
+    pub fn new(porazdelitev: D, popravek_zveznosti: bool) -> Self {
+        Diskretizirana {
+            porazdelitev,
+            zamik: if popravek_zveznosti { 0.5 } else { 0.0 },
+        }
+    }
+}
+
+impl<D: Zvezna> Porazdelitev for Diskretizirana<D> {
+    fn cdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        self.porazdelitev.cdf(x.floor() + self.zamik)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 1.0;
+        }
+        self.porazdelitev.sf(x.floor() + self.zamik)
+    }
+}
+
+impl<D: Zvezna> Diskretna for Diskretizirana<D> {
+    fn pmf(&self, x: u64) -> f64 {
+        let x = x as f64;
+        if x == 0.0 {
+            return self.cdf(0.0);
+        }
+        verjetnost_zvezne(&self.porazdelitev, x - 1.0 + self.zamik, x + self.zamik)
+    }
+}
+
+// napaka približka diskretne porazdelitve na {0, 1, 2, ...}
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct NapakaPriblizka {
+    // max_k |F(k) - G(k)|
+    pub cdf: f64,
+    // 1/2 sum_k |p(k) - q(k)|
+    pub totalna_variacija: f64,
+}
+
+pub fn napaka_priblizka<D, P>(tocna: &D, priblizek: &P) -> NapakaPriblizka
+where
+    D: Diskretna + Kvantil + ?Sized,
+    P: Diskretna + ?Sized,
+{
+    // F in G seštevamo sproti, da je cena linearna v dolžini nosilca
+    let zgornja = tocna.isf(1e-12).min(1e7) as u64;
+    let (mut f, mut g) = (0.0, 0.0);
+    let mut napaka = NapakaPriblizka {
+        cdf: 0.0,
+        totalna_variacija: 0.0,
+    };
+    for k in 0..=zgornja {
+        let (p, q) = (tocna.pmf(k), priblizek.pmf(k));
+        f += p;
+        g += q;
+        napaka.cdf = napaka.cdf.max((f - g).abs());
+        napaka.totalna_variacija += 0.5 * (p - q).abs();
+    }
+    // masa približka nad nosilcem točne porazdelitve
+    napaka.totalna_variacija += 0.5 * priblizek.sf(zgornja as f64);
+    napaka
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priblizki_binomske() {
+        let b = Binomska::new(100, 0.3);
+        let n = b.normalni_priblizek().unwrap();
+        assert_eq!((n.mu, n.sigma2), (30.0, 21.0));
+
+        // popravek za zveznost precej zmanjša napako
+        let brez = napaka_priblizka(&b, &Diskretizirana::new(n.clone(), false));
+        let s_popravkom = napaka_priblizka(&b, &Diskretizirana::new(n, true));
+        assert!(s_popravkom.cdf < 0.25 * brez.cdf);
+        assert!(s_popravkom.cdf < 0.01);
+
+        // napaka Poissonovega približka pada z n pri fiksnem np
+        let napaka = |n: u64| {
+            let b = Binomska::new(n, 5.0 / n as f64);
+            napaka_priblizka(&b, &b.poissonov_priblizek()).totalna_variacija
+        };
+        assert!(napaka(1000) < napaka(100) && napaka(100) < napaka(10));
+        assert!(napaka(1000) < 0.005);
+
+        let d = Diskretizirana::new(Normalna::new(3.0, 4.0), true);
+        let vsota: f64 = (0..100).map(|k| d.pmf(k)).sum();
+        assert!((vsota - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_binomski_priblizek_hipergeometrijske() {
+        let h = Hipergeometrijska::new(300, 10, 1000);
+        let b = h.binomski_priblizek();
+        assert_eq!((b.n, b.p), (10, 0.3));
+        assert!(napaka_priblizka(&h, &b).cdf < 0.01);
+    }
+
+    #[test]
+    fn test_izrojeni_normalni_priblizki() {
+        assert!(Binomska::new(10, 0.0).normalni_priblizek().is_none());
+        assert!(Binomska::new(10, 1.0).normalni_priblizek().is_none());
+        assert!(Binomska::new(0, 0.5).normalni_priblizek().is_none());
+        assert!(Poisson::new(0.0).normalni_priblizek().is_none());
+        assert!(
+            Hipergeometrijska::new(0, 5, 20)
+                .normalni_priblizek()
+                .is_none()
+        );
+        assert!(
+            Hipergeometrijska::new(5, 0, 20)
+                .normalni_priblizek()
+                .is_none()
+        );
+        assert!(
+            Hipergeometrijska::new(20, 5, 20)
+                .normalni_priblizek()
+                .is_none()
+        );
+        assert!(
+            Hipergeometrijska::new(1, 1, 1)
+                .normalni_priblizek()
+                .is_none()
+        );
+        assert!(Poisson::new(0.5).normalni_priblizek().is_some());
+    }
+}
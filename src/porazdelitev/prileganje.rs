@@ -0,0 +1,97 @@
+use crate::porazdelitev::register;
+use crate::porazdelitev::*;
+use crate::testi::{RezultatTesta, test_prileganja};
+
+// porazdelitev, prilagojena podatkom po metodi največjega verjetja, z merami kakovosti
+pub struct Prileganje {
+    pub porazdelitev: Box<dyn DynPorazdelitev>,
+    pub log_verjetje: f64,
+    // Akaikejev kriterij 2k - 2 ln L
+    pub aic: f64,
+    // Bayesov kriterij k ln n - 2 ln L
+    pub bic: f64,
+    // Kolmogorov-Smirnovova razdalja med empirično in prilagojeno cdf
+    pub ks: f64,
+    // χ² za diskretne, KS za zvezne porazdelitve
+    pub test: RezultatTesta,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Kriterij {
+    Aic,
+    Bic,
+    Ks,
+}
+
+impl Prileganje {
+    // manjše je boljše
+    pub fn vrednost(&self, kriterij: Kriterij) -> f64 {
+        match kriterij {
+            Kriterij::Aic => self.aic,
+            Kriterij::Bic => self.bic,
+            Kriterij::Ks => self.ks,
+        }
+    }
+}
+
+// sum ln f(x_i), -inf, če kateri od podatkov ni na nosilcu
+pub fn log_verjetje(d: &dyn DynPorazdelitev, podatki: &[f64]) -> f64 {
+    podatki.iter().map(|&x| d.gostota(x).ln()).sum()
+}
+
+pub fn prilagodi(ime: &str, podatki: &[f64]) -> Result<Prileganje, String> {
+    let porazdelitev = register::prilagodi(ime, podatki)?;
+    let k = porazdelitev.parametri().len() as f64;
+    let n = podatki.len() as f64;
+    let log_verjetje = log_verjetje(porazdelitev.as_ref(), podatki);
+    let test = test_prileganja(podatki, porazdelitev.as_ref(), k as usize);
+    let ks = Empiricna::new(podatki).ks_statistika(porazdelitev.as_ref());
+
+    Ok(Prileganje {
+        porazdelitev,
+        log_verjetje,
+        aic: 2.0 * k - 2.0 * log_verjetje,
+        bic: k * n.ln() - 2.0 * log_verjetje,
+        ks,
+        test,
+    })
+}
+
+// prilagodi vse porazdelitve iz registra, ki ustrezajo podatkom, in jih uredi po kriteriju;
+// pri nenegativnih celih podatkih le diskretne, sicer le zvezne, saj verjetij iz pmf in
+// pdf ne moremo primerjati
+pub fn najboljse_prileganje(podatki: &[f64], kriterij: Kriterij) -> Vec<Prileganje> {
+    let stetje = podatki.iter().all(|&x| x >= 0.0 && x.fract() == 0.0);
+    let mut prileganja: Vec<Prileganje> = register::REGISTER
+        .iter()
+        .filter(|vnos| vnos.diskretna == stetje)
+        .filter_map(|vnos| prilagodi(vnos.ime, podatki).ok())
+        .collect();
+    prileganja.sort_by(|a, b| a.vrednost(kriterij).total_cmp(&b.vrednost(kriterij)));
+    prileganja
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_najboljse_prileganje() {
+        let mut g = Generator::new(3);
+        let vzorec = Normalna::new(2.0, 0.5).vzorci(&mut g, 500);
+        let prileganja = najboljse_prileganje(&vzorec, Kriterij::Aic);
+        assert_eq!(prileganja[0].porazdelitev.ime(), "Normalna");
+        // negativni podatki izključijo eksponentno
+        assert!(prileganja.iter().all(|p| !p.porazdelitev.diskretna()));
+
+        let vzorec = Poisson::new(4.0).vzorci(&mut g, 500);
+        let prileganja = najboljse_prileganje(&vzorec, Kriterij::Bic);
+        assert!(prileganja.iter().all(|p| p.porazdelitev.diskretna()));
+        assert!(
+            prileganja[..2]
+                .iter()
+                .any(|p| p.porazdelitev.ime() == "Poisson")
+        );
+        assert!(prileganja[0].test.p_vrednost > 0.001);
+    }
+}
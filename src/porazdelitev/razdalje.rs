@@ -0,0 +1,104 @@
+use crate::porazdelitev::*;
+use crate::utils;
+
+// razdalje med porazdelitvama, npr. pri primerjavi Binomska(50, 0.1) s Poisson(5)
+
+// skupni nosilec do zanemarljive verjetnosti v repih
+fn nosilec(p: &dyn DynPorazdelitev, q: &dyn DynPorazdelitev) -> (f64, f64) {
+    let od = p.kvantil(1e-12).min(q.kvantil(1e-12));
+    let do_ = p.isf(1e-12).max(q.isf(1e-12));
+    (od, do_)
+}
+
+// cela števila na nosilcu, omejena, da seštevanje ne traja predolgo
+fn cela(od: f64, do_: f64) -> core::ops::RangeInclusive<u64> {
+    let od = od.max(0.0).floor();
+    let do_ = do_.min(od + 1e6).ceil();
+    od as u64..=do_ as u64
+}
+
+// sup |F(x) - G(x)|; pri diskretnih preverimo obe strani vsakega skoka
+pub fn kolmogorova_razdalja(p: &dyn DynPorazdelitev, q: &dyn DynPorazdelitev) -> f64 {
+    let (od, do_) = nosilec(p, q);
+    let razlika = |x: f64| (p.cdf(x) - q.cdf(x)).abs();
+
+    let mut d = (0..=2000)
+        .map(|i| razlika(od + (do_ - od) * i as f64 / 2000.0))
+        .fold(0.0, f64::max);
+    if p.diskretna() || q.diskretna() {
+        for k in cela(od, do_) {
+            let k = k as f64;
+            // tik pred skokom je F(k - ε) = P(X < k), pri zveznih pa kar F(k)
+            let pred = |r: &dyn DynPorazdelitev| {
+                if r.diskretna() {
+                    r.cdf(k - 1.0)
+                } else {
+                    r.cdf(k)
+                }
+            };
+            d = d.max(razlika(k)).max((pred(p) - pred(q)).abs());
+        }
+    }
+    d
+}
+
+// sup_A |P(A) - Q(A)| = 1/2 sum |p - q| oziroma 1/2 int |f - g|;
+// diskretna in zvezna porazdelitev sta singularni, zato je razdalja 1
+pub fn razdalja_totalne_variacije(p: &dyn DynPorazdelitev, q: &dyn DynPorazdelitev) -> f64 {
+    let (od, do_) = nosilec(p, q);
+    let tv = match (p.diskretna(), q.diskretna()) {
+        (true, true) => {
+            0.5 * cela(od, do_)
+                .map(|k| (p.gostota(k as f64) - q.gostota(k as f64)).abs())
+                .sum::<f64>()
+        }
+        (false, false) => 0.5 * utils::integriraj(|x| (p.gostota(x) - q.gostota(x)).abs(), od, do_),
+        _ => 1.0,
+    };
+    tv.clamp(0.0, 1.0)
+}
+
+// sqrt(1 - sum sqrt(p q)) oziroma sqrt(1 - int sqrt(f g)), med 0 in 1
+pub fn hellingerjeva_razdalja(p: &dyn DynPorazdelitev, q: &dyn DynPorazdelitev) -> f64 {
+    let (od, do_) = nosilec(p, q);
+    let bhattacharyya = match (p.diskretna(), q.diskretna()) {
+        (true, true) => cela(od, do_)
+            .map(|k| (p.gostota(k as f64) * q.gostota(k as f64)).sqrt())
+            .sum::<f64>(),
+        (false, false) => utils::integriraj(|x| (p.gostota(x) * q.gostota(x)).sqrt(), od, do_),
+        _ => 0.0,
+    };
+    (1.0 - bhattacharyya).max(0.0).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_razdalje() {
+        let b = Binomska::new(50, 0.1);
+        let p = Poisson::new(5.0);
+        let n = Normalna::new(5.0, 4.5);
+
+        assert_eq!(kolmogorova_razdalja(&b, &b), 0.0);
+        assert!(razdalja_totalne_variacije(&b, &b) < 1e-12);
+
+        // Le Camova neenakost: d_TV(Bin(n, p), Poi(np)) <= n p^2 = 0.5, v resnici je precej manj
+        let tv = razdalja_totalne_variacije(&b, &p);
+        assert!(tv > 0.0 && tv < 0.05);
+        assert!(kolmogorova_razdalja(&b, &p) <= tv + 1e-12);
+        assert_eq!(razdalja_totalne_variacije(&b, &n), 1.0);
+        assert!(kolmogorova_razdalja(&b, &n) < 0.2);
+
+        // med N(0, 1) in N(1, 1) je d_TV = 2 Φ(1/2) - 1 in d_K enaka
+        let n0 = Normalna::new(0.0, 1.0);
+        let n1 = Normalna::new(1.0, 1.0);
+        let pricakovana = 0.382924922548026;
+        assert!((razdalja_totalne_variacije(&n0, &n1) - pricakovana).abs() < 1e-8);
+        assert!((kolmogorova_razdalja(&n0, &n1) - pricakovana).abs() < 1e-6);
+        // H^2 = 1 - exp(-1/8)
+        let h = hellingerjeva_razdalja(&n0, &n1);
+        assert!((h * h - (1.0 - (-0.125f64).exp())).abs() < 1e-8);
+    }
+}
@@ -0,0 +1,536 @@
+use alloc::collections::BTreeMap;
+
+use crate::porazdelitev::*;
+use crate::utils;
+
+pub struct Parameter {
+    pub ime: &'static str,
+    pub opis: &'static str,
+    pub celo_stevilo: bool,
+    pub privzeto: f64,
+    // priporočen razpon, npr. za drsnike v vmesniku
+    pub razpon: (f64, f64),
+}
+
+// parametre preveri in vrne porazdelitev ali opis napake
+pub type Konstruktor = fn(&[f64]) -> Result<Box<dyn DynPorazdelitev>, String>;
+
+// oceni parametre iz podatkov po metodi največjega verjetja (MLE)
+pub type Prilagoditev = fn(&[f64]) -> Result<Vec<f64>, String>;
+
+pub struct Vnos {
+    pub ime: &'static str,
+    pub prikazno_ime: &'static str,
+    // druga imena, ki jih sprejme razčlenjevalnik (velikost črk ni pomembna)
+    pub sinonimi: &'static [&'static str],
+    pub diskretna: bool,
+    pub parametri: &'static [Parameter],
+    pub ustvari: Konstruktor,
+    pub prilagodi: Prilagoditev,
+}
+
+impl Vnos {
+    pub fn privzeti_parametri(&self) -> Vec<f64> {
+        self.parametri.iter().map(|p| p.privzeto).collect()
+    }
+}
+
+const P: Parameter = Parameter {
+    ime: "p",
+    opis: "verjetnost uspeha",
+    celo_stevilo: false,
+    privzeto: 0.5,
+    razpon: (0.01, 0.99),
+};
+
+pub static REGISTER: [Vnos; 10] = [
+    Vnos {
+        ime: "Bernoulli",
+        prikazno_ime: "Bernoullijeva",
+        sinonimi: &["Bernoullijeva", "Ber"],
+        diskretna: true,
+        parametri: &[P],
+        ustvari: |x| {
+            let p = verjetnost(x[0], "p")?;
+            Ok(Box::new(Bernoulli::new(p)))
+        },
+        prilagodi: |x| {
+            if cela_stevila(x)?.iter().any(|&k| k > 1) {
+                return Err("podatki morajo biti 0 ali 1".to_string());
+            }
+            Ok(vec![povprecje(x)])
+        },
+    },
+    Vnos {
+        ime: "Binomska",
+        prikazno_ime: "Binomska",
+        sinonimi: &["Binomial", "Bin"],
+        diskretna: true,
+        parametri: &[
+            Parameter {
+                ime: "n",
+                opis: "število poskusov",
+                celo_stevilo: true,
+                privzeto: 10.0,
+                razpon: (1.0, 100.0),
+            },
+            P,
+        ],
+        ustvari: |x| {
+            let n = omejeno(celo(x[0], "n")?, "n")?;
+            let p = verjetnost(x[1], "p")?;
+            Ok(Box::new(Binomska::new(n, p)))
+        },
+        prilagodi: |x| {
+            let (n, p) = mle_binomske(&cela_stevila(x)?)?;
+            Ok(vec![n as f64, p])
+        },
+    },
+    Vnos {
+        ime: "Eksponentna",
+        prikazno_ime: "Eksponentna",
+        sinonimi: &["Exponential", "Exp"],
+        diskretna: false,
+        parametri: &[Parameter {
+            ime: "lambda",
+            opis: "intenzivnost",
+            celo_stevilo: false,
+            privzeto: 1.0,
+            razpon: (0.1, 10.0),
+        }],
+        ustvari: |x| {
+            let lambda = pozitivno(x[0], "lambda")?;
+            Ok(Box::new(Eksponentna::new(lambda)))
+        },
+        prilagodi: |x| {
+            if x.iter().any(|&x| x <= 0.0) {
+                return Err("podatki morajo biti pozitivni".to_string());
+            }
+            Ok(vec![1.0 / povprecje(x)])
+        },
+    },
+    Vnos {
+        ime: "EnakomernaInterval",
+        prikazno_ime: "Enakomerna na intervalu",
+        sinonimi: &["Enakomerna", "Uniform"],
+        diskretna: false,
+        parametri: &[
+            Parameter {
+                ime: "a",
+                opis: "spodnja meja",
+                celo_stevilo: false,
+                privzeto: 0.0,
+                razpon: (-10.0, 10.0),
+            },
+            Parameter {
+                ime: "b",
+                opis: "zgornja meja",
+                celo_stevilo: false,
+                privzeto: 1.0,
+                razpon: (-10.0, 10.0),
+            },
+        ],
+        ustvari: |x| {
+            let a = koncno(x[0], "a")?;
+            let b = koncno(x[1], "b")?;
+            if a >= b {
+                return Err("a mora biti manjši od b".to_string());
+            }
+            Ok(Box::new(EnakomernaInterval::new(a, b)))
+        },
+        prilagodi: |x| {
+            let a = x.iter().copied().fold(f64::INFINITY, f64::min);
+            let b = x.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            Ok(vec![a, b])
+        },
+    },
+    Vnos {
+        ime: "Geometrijska",
+        prikazno_ime: "Geometrijska",
+        sinonimi: &["Geometric", "Geom"],
+        diskretna: true,
+        parametri: &[P],
+        ustvari: |x| {
+            let p = verjetnost(x[0], "p")?;
+            if p == 0.0 {
+                return Err("p mora biti pozitiven".to_string());
+            }
+            Ok(Box::new(Geometrijska::new(p)))
+        },
+        // nosilec je {1, 2, ...}
+        prilagodi: |x| {
+            if cela_stevila(x)?.contains(&0) {
+                return Err("podatki morajo biti pozitivni".to_string());
+            }
+            Ok(vec![1.0 / povprecje(x)])
+        },
+    },
+    Vnos {
+        ime: "HiKvadrat",
+        prikazno_ime: "χ² (hi kvadrat)",
+        sinonimi: &["ChiSquared", "Chi2"],
+        diskretna: false,
+        parametri: &[Parameter {
+            ime: "k",
+            opis: "prostostne stopnje",
+            celo_stevilo: false,
+            privzeto: 3.0,
+            razpon: (1.0, 30.0),
+        }],
+        ustvari: |x| {
+            let k = pozitivno(x[0], "k")?;
+            Ok(Box::new(HiKvadrat::new(k)))
+        },
+        // MLE za k nima zaprte oblike (enačba z digama funkcijo)
+        prilagodi: |_| Err("ocena parametrov porazdelitve χ² ni podprta".to_string()),
+    },
+    Vnos {
+        ime: "Hipergeometrijska",
+        prikazno_ime: "Hipergeometrijska",
+        sinonimi: &["Hypergeometric", "Hypergeom"],
+        diskretna: true,
+        parametri: &[
+            Parameter {
+                ime: "s",
+                opis: "uspešni elementi v populaciji",
+                celo_stevilo: true,
+                privzeto: 5.0,
+                razpon: (0.0, 100.0),
+            },
+            Parameter {
+                ime: "r",
+                opis: "velikost vzorca",
+                celo_stevilo: true,
+                privzeto: 5.0,
+                razpon: (0.0, 100.0),
+            },
+            Parameter {
+                ime: "n",
+                opis: "velikost populacije",
+                celo_stevilo: true,
+                privzeto: 20.0,
+                razpon: (1.0, 100.0),
+            },
+        ],
+        ustvari: |x| {
+            let s = celo(x[0], "s")?;
+            let r = celo(x[1], "r")?;
+            let n = omejeno(celo(x[2], "n")?, "n")?;
+            if n == 0 {
+                return Err("n mora biti pozitiven".to_string());
+            }
+            if s > n || r > n {
+                return Err("s in r ne smeta biti večja od n".to_string());
+            }
+            Ok(Box::new(Hipergeometrijska::new(s, r, n)))
+        },
+        // trije celoštevilski parametri iz enega vzorca niso določljivi
+        prilagodi: |_| Err("ocena parametrov hipergeometrijske ni podprta".to_string()),
+    },
+    Vnos {
+        ime: "Normalna",
+        prikazno_ime: "Normalna",
+        sinonimi: &["Normal", "Gauss", "N"],
+        diskretna: false,
+        parametri: &[
+            Parameter {
+                ime: "mu",
+                opis: "pričakovana vrednost",
+                celo_stevilo: false,
+                privzeto: 0.0,
+                razpon: (-10.0, 10.0),
+            },
+            Parameter {
+                ime: "sigma2",
+                opis: "varianca",
+                celo_stevilo: false,
+                privzeto: 1.0,
+                razpon: (0.1, 5.0),
+            },
+        ],
+        ustvari: |x| {
+            let mu = koncno(x[0], "mu")?;
+            let sigma2 = pozitivno(x[1], "sigma2")?;
+            Ok(Box::new(Normalna::new(mu, sigma2)))
+        },
+        // MLE variance deli z n, ne z n - 1
+        prilagodi: |x| {
+            let mu = povprecje(x);
+            let sigma2 = x.iter().map(|x| (x - mu) * (x - mu)).sum::<f64>() / x.len() as f64;
+            Ok(vec![mu, sigma2])
+        },
+    },
+    Vnos {
+        ime: "Poisson",
+        prikazno_ime: "Poissonova",
+        sinonimi: &["Poissonova", "Pois"],
+        diskretna: true,
+        parametri: &[Parameter {
+            ime: "lambda",
+            opis: "pričakovano število dogodkov",
+            celo_stevilo: false,
+            privzeto: 1.0,
+            razpon: (0.1, 20.0),
+        }],
+        ustvari: |x| {
+            let lambda = koncno(x[0], "lambda")?;
+            if lambda < 0.0 {
+                return Err("lambda ne sme biti negativna".to_string());
+            }
+            if lambda > MAKS_PARAMETER as f64 {
+                return Err(format!("lambda ne sme biti večja od {}", MAKS_PARAMETER));
+            }
+            Ok(Box::new(Poisson::new(lambda)))
+        },
+        prilagodi: |x| {
+            cela_stevila(x)?;
+            Ok(vec![povprecje(x)])
+        },
+    },
+    Vnos {
+        ime: "StudentovaT",
+        prikazno_ime: "Studentova t",
+        sinonimi: &["Studentova", "StudentT", "T"],
+        diskretna: false,
+        parametri: &[Parameter {
+            ime: "n",
+            opis: "prostostne stopnje",
+            celo_stevilo: false,
+            privzeto: 5.0,
+            razpon: (1.0, 30.0),
+        }],
+        ustvari: |x| {
+            let n = pozitivno(x[0], "n")?;
+            Ok(Box::new(StudentovaT::new(n)))
+        },
+        // MLE za n nima zaprte oblike
+        prilagodi: |_| Err("ocena parametrov Studentove t ni podprta".to_string()),
+    },
+];
+
+pub fn najdi(ime: &str) -> Option<&'static Vnos> {
+    REGISTER.iter().find(|v| v.ime == ime)
+}
+
+// poišče po imenu ali sinonimu, ne glede na velikost črk
+pub fn poisci(ime: &str) -> Option<&'static Vnos> {
+    let ime = ime.to_lowercase();
+    REGISTER
+        .iter()
+        .find(|v| v.ime.to_lowercase() == ime || v.sinonimi.iter().any(|s| s.to_lowercase() == ime))
+}
+
+// preveri število parametrov in ustvari porazdelitev
+pub fn ustvari(ime: &str, parametri: &[f64]) -> Result<Box<dyn DynPorazdelitev>, String> {
+    let vnos = najdi(ime).ok_or_else(|| format!("neznana porazdelitev {}", ime))?;
+    if parametri.len() != vnos.parametri.len() {
+        return Err(format!(
+            "{} potrebuje {} parametrov, podanih je {}",
+            vnos.ime,
+            vnos.parametri.len(),
+            parametri.len()
+        ));
+    }
+    (vnos.ustvari)(parametri)
+}
+
+// oceni parametre in ustvari porazdelitev; napaka, če podatki ne ustrezajo nosilcu
+pub fn prilagodi(ime: &str, podatki: &[f64]) -> Result<Box<dyn DynPorazdelitev>, String> {
+    let vnos = najdi(ime).ok_or_else(|| format!("neznana porazdelitev {}", ime))?;
+    if podatki.is_empty() || !podatki.iter().all(|x| x.is_finite()) {
+        return Err("podatki morajo biti neprazni in končni".to_string());
+    }
+    (vnos.ustvari)(&(vnos.prilagodi)(podatki)?)
+}
+
+fn povprecje(x: &[f64]) -> f64 {
+    x.iter().sum::<f64>() / x.len() as f64
+}
+
+fn cela_stevila(x: &[f64]) -> Result<Vec<u64>, String> {
+    x.iter()
+        .map(|&x| celo(x, "podatek"))
+        .collect::<Result<Vec<u64>, String>>()
+        .map_err(|_| "podatki morajo biti nenegativna cela števila".to_string())
+}
+
+// večjih vrednosti ne prilagajamo, profil verjetja bi bil predrag
+const MAKS_BINOMSKA: u64 = 100_000;
+// največ toliko n nad največjo vrednostjo preizkusimo
+const MAKS_KORAKOV: u64 = 10_000;
+
+// n ni zvezen parameter, zato verjetje profiliramo: za vsak n je najboljši p = x̄ / n;
+// pri preveč razpršenih podatkih verjetje narašča proti Poissonovi limiti, zato n omejimo
+fn mle_binomske(x: &[u64]) -> Result<(u64, f64), String> {
+    let max = x.iter().copied().max().unwrap_or(0);
+    if max > MAKS_BINOMSKA {
+        return Err(format!(
+            "vrednosti nad {} so prevelike za binomsko porazdelitev",
+            MAKS_BINOMSKA
+        ));
+    }
+    let mut frekvence = BTreeMap::new();
+    for &k in x {
+        *frekvence.entry(k).or_insert(0.0) += 1.0;
+    }
+    let stevilo = x.len() as f64;
+    let vsota = x.iter().map(|&k| k as f64).sum::<f64>();
+    let povprecje = vsota / stevilo;
+    // x ln y, pri x = 0 je člen 0 tudi za y = 0
+    let xlny = |x: f64, y: f64| if x == 0.0 { 0.0 } else { x * y.ln() };
+    // brez člena -sum ln k!, ki ni odvisen od n
+    let log_verjetje = |n: u64| {
+        let p = povprecje / n as f64;
+        frekvence
+            .iter()
+            .map(|(&k, &f)| f * (utils::ln_factorial(n) - utils::ln_factorial(n - k)))
+            .sum::<f64>()
+            + xlny(vsota, p)
+            + xlny(stevilo * n as f64 - vsota, 1.0 - p)
+    };
+
+    let zacetek = max.max(1);
+    let mut najboljsi = (zacetek, log_verjetje(zacetek));
+    for n in zacetek + 1..=(10 * max + 100).min(zacetek + MAKS_KORAKOV) {
+        let l = log_verjetje(n);
+        if l > najboljsi.1 {
+            najboljsi = (n, l);
+        } else if l < najboljsi.1 - 10.0 {
+            break;
+        }
+    }
+    Ok((najboljsi.0, povprecje / najboljsi.0 as f64))
+}
+
+fn koncno(x: f64, ime: &str) -> Result<f64, String> {
+    if x.is_finite() {
+        Ok(x)
+    } else {
+        Err(format!("{} mora biti končno število", ime))
+    }
+}
+
+fn pozitivno(x: f64, ime: &str) -> Result<f64, String> {
+    if koncno(x, ime)? > 0.0 {
+        Ok(x)
+    } else {
+        Err(format!("{} mora biti pozitiven", ime))
+    }
+}
+
+fn verjetnost(x: f64, ime: &str) -> Result<f64, String> {
+    if (0.0..=1.0).contains(&x) {
+        Ok(x)
+    } else {
+        Err(format!("{} mora biti na intervalu [0, 1]", ime))
+    }
+}
+
+// nad 2^53 f64 ne loči več sosednjih celih števil
+const MAKS_CELO: f64 = 9_007_199_254_740_992.0;
+
+fn celo(x: f64, ime: &str) -> Result<u64, String> {
+    if koncno(x, ime)? < 0.0 || x.fract() != 0.0 {
+        Err(format!("{} mora biti nenegativno celo število", ime))
+    } else if x > MAKS_CELO {
+        Err(format!("{} je prevelik, da bi ga natančno zapisali", ime))
+    } else {
+        Ok(x as u64)
+    }
+}
+
+// cdf in kvantil diskretnih porazdelitev seštevata pmf člen za členom od 0 naprej, zato
+// velikost parametrov omejimo
+const MAKS_PARAMETER: u64 = 10_000_000;
+
+fn omejeno(n: u64, ime: &str) -> Result<u64, String> {
+    if n <= MAKS_PARAMETER {
+        Ok(n)
+    } else {
+        Err(format!("{} ne sme biti večji od {}", ime, MAKS_PARAMETER))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nakljucno::Generator;
+
+    #[test]
+    fn test_privzeti_parametri() {
+        for vnos in REGISTER.iter() {
+            let parametri = vnos.privzeti_parametri();
+            let d = ustvari(vnos.ime, &parametri).unwrap();
+            assert_eq!(d.ime(), vnos.ime);
+            assert_eq!(d.diskretna(), vnos.diskretna);
+            assert_eq!(d.parametri(), parametri);
+            assert!(d.e().is_finite() && d.var() >= 0.0);
+        }
+    }
+
+    #[test]
+    fn test_prilagodi() {
+        let d = prilagodi("Normalna", &[1.0, 2.0, 3.0, 6.0]).unwrap();
+        assert_eq!(d.parametri(), vec![3.0, 3.5]);
+        let d = prilagodi("Poisson", &[0.0, 2.0, 4.0]).unwrap();
+        assert_eq!(d.parametri(), vec![2.0]);
+        assert!(prilagodi("Poisson", &[0.5, 2.0]).is_err());
+        assert!(prilagodi("Geometrijska", &[0.0, 2.0]).is_err());
+        assert!(prilagodi("Eksponentna", &[-1.0, 2.0]).is_err());
+        assert!(prilagodi("Normalna", &[1.0, 1.0]).is_err());
+
+        // premalo razpršeni podatki: Bin(n, p) z n blizu 10
+        let x: Vec<f64> = [3, 5, 4, 6, 5, 4, 5, 6, 3, 5, 4, 5, 7, 4, 5, 6]
+            .iter()
+            .map(|&k| k as f64)
+            .collect();
+        let d = prilagodi("Binomska", &x).unwrap();
+        let n = d.parametri()[0];
+        assert!((7.0..=20.0).contains(&n));
+        assert!((d.e() - 4.8125).abs() < 1e-12);
+
+        // n = 60, p = 0.3
+        let x: Vec<f64> = Binomska::new(60, 0.3)
+            .vzorci(&mut Generator::new(3), 2000)
+            .iter()
+            .map(|k| k.round())
+            .collect();
+        let n = prilagodi("Binomska", &x).unwrap().parametri()[0];
+        assert!((45.0..=90.0).contains(&n));
+        let d = prilagodi("Binomska", &[0.0, 0.0]).unwrap();
+        assert_eq!(d.parametri(), vec![1.0, 0.0]);
+        // pri velikih vrednostih bi bil profil verjetja predrag
+        assert!(prilagodi("Binomska", &[1.0, 1e9]).is_err());
+        assert!(prilagodi("Binomska", &[1e19]).is_err());
+    }
+
+    #[test]
+    fn test_neveljavni_parametri() {
+        assert!(ustvari("Binomska", &[10.0, 1.5]).is_err());
+        assert!(ustvari("Binomska", &[10.5, 0.5]).is_err());
+        assert!(ustvari("EnakomernaInterval", &[1.0, 0.0]).is_err());
+        assert!(ustvari("Normalna", &[0.0]).is_err());
+        assert!(ustvari("Cauchy", &[0.0, 1.0]).is_err());
+        assert!(ustvari("Binomska", &[1e12, 0.5]).is_err());
+        assert!(ustvari("Binomska", &[1e300, 0.5]).is_err());
+        assert!(ustvari("Hipergeometrijska", &[1e15, 1e15, 1e15]).is_err());
+        assert!(ustvari("Poisson", &[1e15]).is_err());
+        assert!(prilagodi("Poisson", &[0.0, 1e17]).is_err());
+    }
+
+    #[test]
+    fn test_veliki_parametri() {
+        // na meji se pmf, pričakovana vrednost in kvantil še izračunajo pravilno
+        let d = ustvari("Binomska", &[2000.0, 0.5]).unwrap();
+        assert!((d.gostota(1000.0) - 0.017839011146652).abs() < 1e-12);
+        let d = ustvari("Hipergeometrijska", &[1e7, 1e7, 1e7]).unwrap();
+        assert_eq!(d.e(), 1e7);
+        let d = ustvari("Hipergeometrijska", &[5e6, 2000.0, 1e7]).unwrap();
+        assert!((d.e() - 1000.0).abs() < 1e-9);
+        assert!((d.gostota(1000.0) - 0.017839).abs() < 1e-5);
+        let d = ustvari("Geometrijska", &[1e-300]).unwrap();
+        let mediana = d.kvantil(0.5);
+        assert!((mediana / (2f64.ln() * 1e300) - 1.0).abs() < 1e-12);
+        assert!((d.cdf(mediana) - 0.5).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,502 @@
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::porazdelitev::*;
+use crate::porazdelitev::{okrnjena, register};
+
+// oblika {"type": ..., parametri...}; porazdelitve se berejo prek tega tipa,
+// da parametre preverimo, preden pokličemo konstruktor
+#[derive(serde::Deserialize)]
+#[serde(tag = "type", deny_unknown_fields)]
+pub(crate) enum Serializirana {
+    Bernoulli {
+        p: f64,
+    },
+    Binomska {
+        n: u64,
+        p: f64,
+    },
+    Eksponentna {
+        lambda: f64,
+    },
+    EnakomernaInterval {
+        a: f64,
+        b: f64,
+    },
+    Geometrijska {
+        p: f64,
+    },
+    Hipergeometrijska {
+        s: u64,
+        r: u64,
+        n: u64,
+    },
+    Normalna {
+        mu: f64,
+        sigma2: f64,
+    },
+    Poisson {
+        lambda: f64,
+    },
+    StudentovaT {
+        n: f64,
+    },
+    HiKvadrat {
+        k: f64,
+    },
+    KoncnaDiskretna {
+        verjetnosti: Vec<f64>,
+    },
+    Empiricna {
+        podatki: Vec<f64>,
+        interpolacija: Interpolacija,
+    },
+    JedrnaOcena {
+        podatki: Vec<f64>,
+        jedro: Jedro,
+        h: f64,
+    },
+}
+
+impl Serializirana {
+    // ime in parametri za porazdelitve iz registra
+    fn registrirana(&self) -> Option<(&'static str, Vec<f64>)> {
+        Some(match *self {
+            Serializirana::Bernoulli { p } => ("Bernoulli", vec![p]),
+            Serializirana::Binomska { n, p } => ("Binomska", vec![n as f64, p]),
+            Serializirana::Eksponentna { lambda } => ("Eksponentna", vec![lambda]),
+            Serializirana::EnakomernaInterval { a, b } => ("EnakomernaInterval", vec![a, b]),
+            Serializirana::Geometrijska { p } => ("Geometrijska", vec![p]),
+            Serializirana::Hipergeometrijska { s, r, n } => {
+                ("Hipergeometrijska", vec![s as f64, r as f64, n as f64])
+            }
+            Serializirana::Normalna { mu, sigma2 } => ("Normalna", vec![mu, sigma2]),
+            Serializirana::Poisson { lambda } => ("Poisson", vec![lambda]),
+            Serializirana::StudentovaT { n } => ("StudentovaT", vec![n]),
+            Serializirana::HiKvadrat { k } => ("HiKvadrat", vec![k]),
+            _ => return None,
+        })
+    }
+
+    fn ime(&self) -> &'static str {
+        match self {
+            Serializirana::KoncnaDiskretna { .. } => "KoncnaDiskretna",
+            Serializirana::Empiricna { .. } => "Empiricna",
+            Serializirana::JedrnaOcena { .. } => "JedrnaOcena",
+            _ => self.registrirana().unwrap().0,
+        }
+    }
+
+    // preverjanje prepustimo registru, ki ima ista pravila kot konstruktorji
+    fn preveri(&self) -> Result<(), String> {
+        match self {
+            Serializirana::KoncnaDiskretna { verjetnosti } => {
+                if !verjetnosti.iter().all(|p| p.is_finite() && *p >= 0.0) {
+                    return Err("verjetnosti morajo biti nenegativne".to_string());
+                }
+                if verjetnosti.iter().sum::<f64>() <= 0.0 {
+                    return Err("vsota verjetnosti mora biti pozitivna".to_string());
+                }
+                Ok(())
+            }
+            Serializirana::Empiricna { podatki, .. } => {
+                if podatki.is_empty() || podatki.iter().any(|x| x.is_nan()) {
+                    return Err("podatki morajo biti neprazni in brez NaN".to_string());
+                }
+                Ok(())
+            }
+            Serializirana::JedrnaOcena { podatki, h, .. } => {
+                if podatki.len() < 2 || !podatki.iter().all(|x| x.is_finite()) {
+                    return Err("potrebni sta vsaj dve končni točki".to_string());
+                }
+                if !(h.is_finite() && *h > 0.0) {
+                    return Err("h mora biti pozitiven".to_string());
+                }
+                Ok(())
+            }
+            _ => {
+                let (ime, parametri) = self.registrirana().unwrap();
+                register::ustvari(ime, &parametri).map(|_| ())
+            }
+        }
+    }
+
+    fn napacen_tip(&self, pricakovan: &str) -> String {
+        format!(
+            "pričakovana porazdelitev {}, podana {}",
+            pricakovan,
+            self.ime()
+        )
+    }
+}
+
+impl TryFrom<Serializirana> for Box<dyn DynPorazdelitev> {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        match s.registrirana() {
+            Some((ime, parametri)) => register::ustvari(ime, &parametri),
+            None => Err(format!("{} ni v registru porazdelitev", s.ime())),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for Bernoulli {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::Bernoulli { p } => Ok(Bernoulli::new(p)),
+            _ => Err(s.napacen_tip("Bernoulli")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for Binomska {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::Binomska { n, p } => Ok(Binomska::new(n, p)),
+            _ => Err(s.napacen_tip("Binomska")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for Eksponentna {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::Eksponentna { lambda } => Ok(Eksponentna::new(lambda)),
+            _ => Err(s.napacen_tip("Eksponentna")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for EnakomernaInterval {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::EnakomernaInterval { a, b } => Ok(EnakomernaInterval::new(a, b)),
+            _ => Err(s.napacen_tip("EnakomernaInterval")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for Geometrijska {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::Geometrijska { p } => Ok(Geometrijska::new(p)),
+            _ => Err(s.napacen_tip("Geometrijska")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for Hipergeometrijska {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::Hipergeometrijska { s, r, n } => Ok(Hipergeometrijska::new(s, r, n)),
+            _ => Err(s.napacen_tip("Hipergeometrijska")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for Normalna {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::Normalna { mu, sigma2 } => Ok(Normalna::new(mu, sigma2)),
+            _ => Err(s.napacen_tip("Normalna")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for Poisson {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::Poisson { lambda } => Ok(Poisson::new(lambda)),
+            _ => Err(s.napacen_tip("Poisson")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for StudentovaT {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::StudentovaT { n } => Ok(StudentovaT::new(n)),
+            _ => Err(s.napacen_tip("StudentovaT")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for HiKvadrat {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::HiKvadrat { k } => Ok(HiKvadrat::new(k)),
+            _ => Err(s.napacen_tip("HiKvadrat")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for KoncnaDiskretna {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::KoncnaDiskretna { verjetnosti } => Ok(KoncnaDiskretna::new(verjetnosti)),
+            _ => Err(s.napacen_tip("KoncnaDiskretna")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for Empiricna {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::Empiricna {
+                podatki,
+                interpolacija,
+            } => {
+                let mut e = Empiricna::new(&podatki);
+                e.interpolacija = interpolacija;
+                Ok(e)
+            }
+            _ => Err(s.napacen_tip("Empiricna")),
+        }
+    }
+}
+
+impl TryFrom<Serializirana> for JedrnaOcena {
+    type Error = String;
+
+    fn try_from(s: Serializirana) -> Result<Self, String> {
+        s.preveri()?;
+        match s {
+            Serializirana::JedrnaOcena { podatki, jedro, h } => {
+                Ok(JedrnaOcena::new(&podatki, jedro, SirinaPasu::Fiksna(h)))
+            }
+            _ => Err(s.napacen_tip("JedrnaOcena")),
+        }
+    }
+}
+
+// porazdelitve iz registra zapišemo z imeni parametrov iz registra
+impl Serialize for dyn DynPorazdelitev {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let vnos = register::najdi(self.ime()).unwrap();
+        let mut map = serializer.serialize_map(Some(vnos.parametri.len() + 1))?;
+        map.serialize_entry("type", self.ime())?;
+        for (p, x) in vnos.parametri.iter().zip(self.parametri()) {
+            if p.celo_stevilo {
+                map.serialize_entry(p.ime, &(x as u64))?;
+            } else {
+                map.serialize_entry(p.ime, &x)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Box<dyn DynPorazdelitev> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = Serializirana::deserialize(deserializer)?;
+        Box::<dyn DynPorazdelitev>::try_from(s).map_err(de::Error::custom)
+    }
+}
+
+// {"type": "Okrnjena", "porazdelitev": {...}, "a": ..., "b": ...}; neskončni meji izpustimo,
+// ker JSON ne pozna neskončnosti
+impl<D: Serialize> Serialize for Okrnjena<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", "Okrnjena")?;
+        map.serialize_entry("porazdelitev", self.porazdelitev())?;
+        if self.a().is_finite() {
+            map.serialize_entry("a", &self.a())?;
+        }
+        if self.b().is_finite() {
+            map.serialize_entry("b", &self.b())?;
+        }
+        map.end()
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "type", deny_unknown_fields)]
+enum SerializiranaOkrnjena<D> {
+    Okrnjena {
+        porazdelitev: D,
+        #[serde(default)]
+        a: Option<f64>,
+        #[serde(default)]
+        b: Option<f64>,
+    },
+}
+
+impl<'de, D: Porazdelitev + Deserialize<'de>> Deserialize<'de> for Okrnjena<D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let SerializiranaOkrnjena::Okrnjena { porazdelitev, a, b } =
+            SerializiranaOkrnjena::deserialize(deserializer)?;
+        let a = a.unwrap_or(f64::NEG_INFINITY);
+        let b = b.unwrap_or(f64::INFINITY);
+        if a.is_nan() || b.is_nan() || a >= b {
+            return Err(de::Error::custom("a mora biti manjši od b"));
+        }
+        let (masa, _) = okrnjena::masa(&porazdelitev, a, b);
+        if masa.is_nan() || masa <= 0.0 {
+            return Err(de::Error::custom(
+                "interval mora imeti pozitivno verjetnost",
+            ));
+        }
+        Ok(Okrnjena::new(porazdelitev, a, b))
+    }
+}
+
+// {"type": "Vsota", "x": {...}, "y": {...}}
+impl<A: Serialize, B: Serialize> Serialize for Vsota<A, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("type", "Vsota")?;
+        map.serialize_entry("x", &self.x)?;
+        map.serialize_entry("y", &self.y)?;
+        map.end()
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "type", deny_unknown_fields)]
+enum SerializiranaVsota<A, B> {
+    Vsota { x: A, y: B },
+}
+
+// sestavini se preverita ob branju, vsota sama nima dodatnih pogojev
+impl<'de, A, B> Deserialize<'de> for Vsota<A, B>
+where
+    A: Zvezna + Kvantil + Deserialize<'de>,
+    B: Zvezna + Kvantil + Deserialize<'de>,
+{
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let SerializiranaVsota::Vsota { x, y } = SerializiranaVsota::deserialize(deserializer)?;
+        Ok(Vsota::new(x, y))
+    }
+}
+
+// Mesanica nima zapisa: komponente so dyn ZveznaKomponenta ali dyn DiskretnaKomponenta,
+// iz zapisa pa ne moremo izbrati konkretnega tipa, ki ni v registru (npr. Okrnjena<D>);
+// uteži in komponente je treba zapisati posamezno
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zapis() {
+        let json = serde_json::to_string(&Poisson::new(3.0)).unwrap();
+        assert_eq!(json, r#"{"type":"Poisson","lambda":3.0}"#);
+        let json = serde_json::to_string(&Binomska::new(20, 0.3)).unwrap();
+        assert_eq!(json, r#"{"type":"Binomska","n":20,"p":0.3}"#);
+
+        let b: Binomska = serde_json::from_str(&json).unwrap();
+        assert_eq!(b, Binomska::new(20, 0.3));
+        let d: Box<dyn DynPorazdelitev> = serde_json::from_str(&json).unwrap();
+        assert_eq!(d.parametri(), vec![20.0, 0.3]);
+        assert_eq!(serde_json::to_string(&*d).unwrap(), json);
+        let json = r#"{"type":"StudentovaT","n":3.5}"#;
+        let d: Box<dyn DynPorazdelitev> = serde_json::from_str(json).unwrap();
+        assert_eq!(d.ime(), "StudentovaT");
+        assert_eq!(serde_json::to_string(&*d).unwrap(), json);
+
+        let o = Okrnjena::new(Normalna::new(0.0, 1.0), -1.0, f64::INFINITY);
+        let json = serde_json::to_string(&o).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"Okrnjena","porazdelitev":{"type":"Normalna","mu":0.0,"sigma2":1.0},"a":-1.0}"#
+        );
+        let p: Okrnjena<Normalna> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            (p.porazdelitev(), p.a(), p.b()),
+            (o.porazdelitev(), o.a(), o.b())
+        );
+        assert_eq!(p.cdf(0.5), o.cdf(0.5));
+
+        let v = Vsota::new(Normalna::new(0.0, 1.0), Eksponentna::new(2.0));
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"Vsota","x":{"type":"Normalna","mu":0.0,"sigma2":1.0},"y":{"type":"Eksponentna","lambda":2.0}}"#
+        );
+        let w: Vsota<Normalna, Eksponentna> = serde_json::from_str(&json).unwrap();
+        assert_eq!((w.x, w.y), (v.x, v.y));
+        assert!(
+            serde_json::from_str::<Vsota<Normalna, Eksponentna>>(
+                r#"{"type":"Vsota","x":{"type":"Normalna","mu":0.0,"sigma2":-1.0},"y":{"type":"Eksponentna","lambda":2.0}}"#
+            )
+            .is_err()
+        );
+
+        let e = Empiricna::new(&[3.0, 1.0, 2.0]);
+        let json = serde_json::to_string(&e).unwrap();
+        assert_eq!(serde_json::from_str::<Empiricna>(&json).unwrap(), e);
+    }
+
+    #[test]
+    fn test_neveljavni_zapis() {
+        assert!(serde_json::from_str::<Binomska>(r#"{"type":"Binomska","n":20,"p":1.3}"#).is_err());
+        assert!(serde_json::from_str::<Binomska>(r#"{"type":"Poisson","lambda":1.0}"#).is_err());
+        assert!(serde_json::from_str::<Poisson>(r#"{"type":"Poisson","lambda":-1.0}"#).is_err());
+        assert!(serde_json::from_str::<Poisson>(r#"{"type":"Poisson","mu":1.0}"#).is_err());
+        assert!(
+            serde_json::from_str::<Normalna>(r#"{"type":"Normalna","mu":0.0,"sigma2":0.0}"#)
+                .is_err()
+        );
+        assert!(
+            serde_json::from_str::<KoncnaDiskretna>(
+                r#"{"type":"KoncnaDiskretna","verjetnosti":[]}"#
+            )
+            .is_err()
+        );
+        assert!(
+            serde_json::from_str::<Okrnjena<Poisson>>(
+                r#"{"type":"Okrnjena","porazdelitev":{"type":"Poisson","lambda":1.0},"a":2.0,"b":1.0}"#
+            )
+            .is_err()
+        );
+        assert!(
+            serde_json::from_str::<Okrnjena<Eksponentna>>(
+                r#"{"type":"Okrnjena","porazdelitev":{"type":"Eksponentna","lambda":1.0},"b":-1.0}"#
+            )
+            .is_err()
+        );
+        assert!(
+            serde_json::from_str::<Box<dyn DynPorazdelitev>>(r#"{"type":"Normalna","mu":0.0}"#)
+                .is_err()
+        );
+        assert!(serde_json::from_str::<HiKvadrat>(r#"{"type":"HiKvadrat","k":0.0}"#).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
+use crate::porazdelitev::*;
+use crate::utils;
+
+// Studentova t z n prostostnimi stopnjami, n je lahko necelo
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", try_from = "Serializirana"))]
+pub struct StudentovaT {
+    pub n: f64,
+}
+
+impl StudentovaT {
+    pub fn new(n: f64) -> Self {
+        assert!(n > 0.0 && n.is_finite());
+        StudentovaT { n }
+    }
+
+    // P(T > |t|) = I_(n / (n + t^2))(n / 2, 1 / 2) / 2
+    fn rep(&self, t: f64) -> f64 {
+        if t.is_infinite() {
+            return 0.0;
+        }
+        0.5 * utils::beta_i(0.5 * self.n, 0.5, self.n / (self.n + t * t))
+    }
+}
+
+impl fmt::Display for StudentovaT {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "StudentovaT(n={})", self.n)
+    }
+}
+
+impl Porazdelitev for StudentovaT {
+    fn cdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            self.rep(x)
+        } else {
+            1.0 - self.rep(x)
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        self.cdf(-x)
+    }
+}
+
+impl Zvezna for StudentovaT {
+    fn pdf(&self, x: f64) -> f64 {
+        let n = self.n;
+        let ln_c = utils::ln_gama(0.5 * (n + 1.0))
+            - utils::ln_gama(0.5 * n)
+            - 0.5 * (n * core::f64::consts::PI).ln();
+        (ln_c - 0.5 * (n + 1.0) * (x * x / n).ln_1p()).exp()
+    }
+}
+
+impl PricakovanaVrednost for StudentovaT {
+    // pri n <= 1 ne obstaja
+    fn e(&self) -> f64 {
+        if self.n > 1.0 { 0.0 } else { f64::NAN }
+    }
+}
+
+impl Varianca for StudentovaT {
+    fn var(&self) -> f64 {
+        if self.n > 2.0 {
+            self.n / (self.n - 2.0)
+        } else if self.n > 1.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+impl Kvantil for StudentovaT {
+    // zaradi simetrije iščemo le pozitivne kvantile
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        if p < 0.5 {
+            return -self.isf(p);
+        }
+        self.isf(1.0 - p)
+    }
+
+    fn isf(&self, q: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&q));
+        if q > 0.5 {
+            return -self.isf(1.0 - q);
+        }
+        if q == 0.0 {
+            return f64::INFINITY;
+        }
+        let mut hi = 1.0;
+        while self.rep(hi) > q {
+            hi *= 2.0;
+        }
+        utils::bisekcija(|t| q - self.rep(t), 0.0, hi)
+    }
+}
+
+impl Vzorcenje for StudentovaT {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.kvantil(g.enakomerno())
+    }
+}
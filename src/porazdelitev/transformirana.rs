@@ -0,0 +1,347 @@
+use core::fmt;
+
+use crate::nakljucno::Generator;
+use crate::porazdelitev::*;
+use crate::utils;
+
+// strogo monotona preslikava g, Y = g(X)
+pub enum Preslikava {
+    // a x + b, a != 0
+    Afina {
+        a: f64,
+        b: f64,
+    },
+    Exp,
+    // X mora biti pozitivna
+    Ln,
+    // x^k, k != 0; X mora biti pozitivna
+    Potenca(f64),
+    // poljubna monotona g, podana z inverzom in odvodom
+    Splosna {
+        g: Box<dyn Fn(f64) -> f64>,
+        inverz: Box<dyn Fn(f64) -> f64>,
+        odvod: Box<dyn Fn(f64) -> f64>,
+        narascajoca: bool,
+    },
+}
+
+impl Preslikava {
+    pub fn uporabi(&self, x: f64) -> f64 {
+        match self {
+            Preslikava::Afina { a, b } => a * x + b,
+            Preslikava::Exp => x.exp(),
+            Preslikava::Ln => x.ln(),
+            Preslikava::Potenca(k) => x.powf(*k),
+            Preslikava::Splosna { g, .. } => g(x),
+        }
+    }
+
+    // g^-1(y); zunaj zaloge vrednosti vrne ustrezno mejo definicijskega območja
+    pub fn inverz(&self, y: f64) -> f64 {
+        match self {
+            Preslikava::Afina { a, b } => (y - b) / a,
+            Preslikava::Exp => {
+                if y <= 0.0 {
+                    f64::NEG_INFINITY
+                } else {
+                    y.ln()
+                }
+            }
+            Preslikava::Ln => y.exp(),
+            Preslikava::Potenca(k) => {
+                if y > 0.0 {
+                    y.powf(1.0 / k)
+                } else if *k > 0.0 {
+                    0.0
+                } else {
+                    f64::INFINITY
+                }
+            }
+            Preslikava::Splosna { inverz, .. } => inverz(y),
+        }
+    }
+
+    // g'(x)
+    pub fn odvod(&self, x: f64) -> f64 {
+        match self {
+            Preslikava::Afina { a, .. } => *a,
+            Preslikava::Exp => x.exp(),
+            Preslikava::Ln => 1.0 / x,
+            Preslikava::Potenca(k) => k * x.powf(k - 1.0),
+            Preslikava::Splosna { odvod, .. } => odvod(x),
+        }
+    }
+
+    pub fn narascajoca(&self) -> bool {
+        match self {
+            Preslikava::Afina { a, .. } => *a > 0.0,
+            Preslikava::Exp | Preslikava::Ln => true,
+            Preslikava::Potenca(k) => *k > 0.0,
+            Preslikava::Splosna { narascajoca, .. } => *narascajoca,
+        }
+    }
+}
+
+// porazdelitev Y = g(X) za zvezno X in monotono g
+pub struct Transformirana<D> {
+    pub porazdelitev: D,
+    pub preslikava: Preslikava,
+}
+
+impl<D: Zvezna + Kvantil> Transformirana<D> {
+    // None, če g ni strogo monotona (a = 0 ali k = 0) ali če ni definirana na nosilcu X
+    // (ln in x^k zahtevata X >= 0)
+    pub fn new(porazdelitev: D, preslikava: Preslikava) -> Option<Self> {
+        let veljavna = match preslikava {
+            Preslikava::Afina { a, .. } => a != 0.0 && a.is_finite(),
+            Preslikava::Potenca(k) => k != 0.0 && porazdelitev.kvantil(0.0) >= 0.0,
+            Preslikava::Ln => porazdelitev.kvantil(0.0) >= 0.0,
+            Preslikava::Exp | Preslikava::Splosna { .. } => true,
+        };
+        veljavna.then_some(Transformirana {
+            porazdelitev,
+            preslikava,
+        })
+    }
+
+    // (X - E[X]) / sqrt(Var[X])
+    pub fn standardizirana(porazdelitev: D) -> Self
+    where
+        D: PricakovanaVrednost + Varianca,
+    {
+        let sigma = porazdelitev.var().sqrt();
+        let a = 1.0 / sigma;
+        let b = -porazdelitev.e() / sigma;
+        Transformirana {
+            porazdelitev,
+            preslikava: Preslikava::Afina { a, b },
+        }
+    }
+
+    // E[h(g(X))], integral razdelimo na kvantilih X
+    fn pricakovana<H>(&self, h: H) -> f64
+    where
+        H: Fn(f64) -> f64,
+    {
+        let d = &self.porazdelitev;
+        let mut tocke = vec![d.kvantil(0.0)];
+        for p in [0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999] {
+            let x = d.kvantil(p);
+            if x > *tocke.last().unwrap() {
+                tocke.push(x);
+            }
+        }
+        tocke.push(d.kvantil(1.0));
+
+        tocke
+            .windows(2)
+            .map(|t| utils::integriraj(|x| h(self.preslikava.uporabi(x)) * d.pdf(x), t[0], t[1]))
+            .sum()
+    }
+}
+
+// y = g(x), npr. "2x + 1" ali "ln(x)"
+impl fmt::Display for Preslikava {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Preslikava::Afina { a, b } => write!(f, "{}x + {}", a, b),
+            Preslikava::Exp => write!(f, "exp(x)"),
+            Preslikava::Ln => write!(f, "ln(x)"),
+            Preslikava::Potenca(k) => write!(f, "x^{}", k),
+            Preslikava::Splosna { .. } => write!(f, "g(x)"),
+        }
+    }
+}
+
+impl<D: fmt::Display> fmt::Display for Transformirana<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Transformirana({}, {})",
+            self.porazdelitev, self.preslikava
+        )
+    }
+}
+
+impl<D: Zvezna + Kvantil> Porazdelitev for Transformirana<D> {
+    fn cdf(&self, x: f64) -> f64 {
+        let t = self.preslikava.inverz(x);
+        if self.preslikava.narascajoca() {
+            self.porazdelitev.cdf(t)
+        } else {
+            self.porazdelitev.sf(t)
+        }
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        let t = self.preslikava.inverz(x);
+        if self.preslikava.narascajoca() {
+            self.porazdelitev.sf(t)
+        } else {
+            self.porazdelitev.cdf(t)
+        }
+    }
+}
+
+// f_Y(y) = f_X(g^-1(y)) / |g'(g^-1(y))|
+impl<D: Zvezna + Kvantil> Zvezna for Transformirana<D> {
+    fn pdf(&self, x: f64) -> f64 {
+        let t = self.preslikava.inverz(x);
+        let odvod = self.preslikava.odvod(t).abs();
+        if !t.is_finite() || odvod == 0.0 || !odvod.is_finite() {
+            return 0.0;
+        }
+
+        self.porazdelitev.pdf(t) / odvod
+    }
+}
+
+impl<D: Zvezna + Kvantil + PricakovanaVrednost> PricakovanaVrednost for Transformirana<D> {
+    fn e(&self) -> f64 {
+        match self.preslikava {
+            Preslikava::Afina { a, b } => a * self.porazdelitev.e() + b,
+            _ => self.pricakovana(|y| y),
+        }
+    }
+}
+
+impl<D: Zvezna + Kvantil + PricakovanaVrednost + Varianca> Varianca for Transformirana<D> {
+    fn var(&self) -> f64 {
+        match self.preslikava {
+            Preslikava::Afina { a, .. } => a * a * self.porazdelitev.var(),
+            _ => {
+                let e = self.e();
+                self.pricakovana(|y| (y - e).powi(2))
+            }
+        }
+    }
+}
+
+impl<D: Zvezna + Kvantil> Kvantil for Transformirana<D> {
+    fn kvantil(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p));
+        if self.preslikava.narascajoca() {
+            self.preslikava.uporabi(self.porazdelitev.kvantil(p))
+        } else {
+            self.preslikava.uporabi(self.porazdelitev.isf(p))
+        }
+    }
+
+    fn isf(&self, q: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&q));
+        if self.preslikava.narascajoca() {
+            self.preslikava.uporabi(self.porazdelitev.isf(q))
+        } else {
+            self.preslikava.uporabi(self.porazdelitev.kvantil(q))
+        }
+    }
+}
+
+impl<D: Zvezna + Kvantil + Vzorcenje> Vzorcenje for Transformirana<D> {
+    fn vzorec(&self, g: &mut Generator) -> f64 {
+        self.preslikava.uporabi(self.porazdelitev.vzorec(g))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standardizacija() {
+        let z = Transformirana::standardizirana(Normalna::new(3.0, 4.0));
+        let n = Normalna::new(0.0, 1.0);
+        assert!((z.pdf(0.7) - n.pdf(0.7)).abs() < 1e-15);
+        assert!((z.cdf(-1.2) - n.cdf(-1.2)).abs() < 1e-15);
+        assert!((z.kvantil(0.9) - n.kvantil(0.9)).abs() < 1e-12);
+        assert_eq!(z.e(), 0.0);
+        assert_eq!(z.var(), 1.0);
+    }
+
+    #[test]
+    fn test_logaritemsko_normalna() {
+        let (mu, sigma2) = (0.5, 0.25);
+        let ln = Transformirana::new(Normalna::new(mu, sigma2), Preslikava::Exp).unwrap();
+        let y = 2.0f64;
+        let gostota = (-(y.ln() - mu).powi(2) / (2.0 * sigma2)).exp()
+            / (y * (2.0 * core::f64::consts::PI * sigma2).sqrt());
+        assert!((ln.pdf(y) - gostota).abs() < 1e-15);
+        assert!((ln.kvantil(0.5) - mu.exp()).abs() < 1e-12);
+        assert!((ln.e() - (mu + 0.5 * sigma2).exp()).abs() < 1e-9);
+        let var = (sigma2.exp() - 1.0) * (2.0 * mu + sigma2).exp();
+        assert!((ln.var() - var).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_padajoca_afina() {
+        let x = Normalna::new(1.0, 4.0);
+        let y = Transformirana::new(x.clone(), Preslikava::Afina { a: -2.0, b: 3.0 }).unwrap();
+        // Y = 3 - 2X, cdf Y je sf X
+        for t in [-5.0, 0.0, 1.0, 4.0] {
+            let u = (t - 3.0) / -2.0;
+            assert!((y.cdf(t) - x.sf(u)).abs() < 1e-15);
+            assert!((y.sf(t) - x.cdf(u)).abs() < 1e-15);
+            assert!((y.pdf(t) - x.pdf(u) / 2.0).abs() < 1e-15);
+        }
+        assert!((y.kvantil(0.9) - (3.0 - 2.0 * x.kvantil(0.1))).abs() < 1e-12);
+        assert_eq!((y.e(), y.var()), (1.0, 16.0));
+    }
+
+    #[test]
+    fn test_ln_in_potenca() {
+        // Y = ln X za X ~ Exp(1): P(Y <= y) = 1 - exp(-e^y)
+        let y = Transformirana::new(Eksponentna::new(1.0), Preslikava::Ln).unwrap();
+        for t in [-2.0, 0.0, 1.0f64] {
+            assert!((y.cdf(t) + (-t.exp()).exp_m1()).abs() < 1e-14);
+            assert!((y.pdf(t) - (t - t.exp()).exp()).abs() < 1e-14);
+        }
+        // Euler-Mascheronijeva konstanta
+        assert!((y.e() + 0.5772156649015329).abs() < 1e-7);
+
+        // Y = X^2 za X ~ U(0, 1): P(Y <= y) = sqrt(y)
+        let y = Transformirana::new(EnakomernaInterval::new(0.0, 1.0), Preslikava::Potenca(2.0))
+            .unwrap();
+        assert!((y.cdf(0.25) - 0.5).abs() < 1e-15);
+        assert!((y.pdf(0.25) - 1.0).abs() < 1e-15);
+        assert!((y.e() - 1.0 / 3.0).abs() < 1e-9);
+        // Y = 1 / X za X ~ U(1, 2) je padajoča: P(Y <= y) = 2 - 1 / y
+        let y = Transformirana::new(EnakomernaInterval::new(1.0, 2.0), Preslikava::Potenca(-1.0))
+            .unwrap();
+        assert!((y.cdf(0.8) - 0.75).abs() < 1e-15);
+        assert!((y.kvantil(0.75) - 0.8).abs() < 1e-12);
+        assert!((y.e() - 2f64.ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_splosna() {
+        // Y = X^3 za standardno normalno X
+        let x = Normalna::new(0.0, 1.0);
+        let y = Transformirana::new(
+            x.clone(),
+            Preslikava::Splosna {
+                g: Box::new(|x| x * x * x),
+                inverz: Box::new(|y: f64| y.cbrt()),
+                odvod: Box::new(|x| 3.0 * x * x),
+                narascajoca: true,
+            },
+        )
+        .unwrap();
+        assert!((y.cdf(8.0) - x.cdf(2.0)).abs() < 1e-15);
+        assert!((y.pdf(8.0) - x.pdf(2.0) / 12.0).abs() < 1e-15);
+        assert!((y.kvantil(0.975) - x.kvantil(0.975).powi(3)).abs() < 1e-12);
+        assert!(y.e().abs() < 1e-9);
+        assert!((y.var() - 15.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_neveljavne_preslikave() {
+        let n = || Normalna::new(0.0, 1.0);
+        assert!(Transformirana::new(n(), Preslikava::Ln).is_none());
+        assert!(Transformirana::new(n(), Preslikava::Potenca(2.0)).is_none());
+        assert!(Transformirana::new(n(), Preslikava::Potenca(0.5)).is_none());
+        assert!(Transformirana::new(n(), Preslikava::Afina { a: 0.0, b: 1.0 }).is_none());
+        let e = || Eksponentna::new(1.0);
+        assert!(Transformirana::new(e(), Preslikava::Potenca(0.0)).is_none());
+        assert!(Transformirana::new(e(), Preslikava::Potenca(0.5)).is_some());
+    }
+}
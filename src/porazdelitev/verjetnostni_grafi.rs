@@ -0,0 +1,125 @@
+use crate::porazdelitev::*;
+use crate::testi::kolmogorov_sf;
+
+// točke za grafa Q-Q in P-P: i-ti element urejenega vzorca primerjamo s položajem p_i
+
+// p_i = (i - a) / (n + 1 - 2a) za i = 1, ..., n
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Polozaji {
+    // a = 3/8, skoraj nepristranski kvantili normalne porazdelitve
+    Blom,
+    // a = 1/2, sredine stopnic empirične cdf
+    Hazen,
+    // a = 0, i / (n + 1)
+    Weibull,
+}
+
+impl Polozaji {
+    pub fn p(self, i: usize, n: usize) -> f64 {
+        assert!(1 <= i && i <= n);
+        let a = match self {
+            Polozaji::Blom => 0.375,
+            Polozaji::Hazen => 0.5,
+            Polozaji::Weibull => 0.0,
+        };
+        (i as f64 - a) / (n as f64 + 1.0 - 2.0 * a)
+    }
+}
+
+pub fn polozaji(n: usize, polozaji: Polozaji) -> Vec<f64> {
+    (1..=n).map(|i| polozaji.p(i, n)).collect()
+}
+
+fn urejen(vzorec: &[f64]) -> Vec<f64> {
+    let mut x = vzorec.to_vec();
+    x.sort_by(|a, b| a.total_cmp(b));
+    x
+}
+
+// pari (Q(p_i), x_(i)); če se vzorec prilega, ležijo blizu premice y = x
+pub fn qq_tocke<D: Kvantil + ?Sized>(vzorec: &[f64], d: &D, polozaji: Polozaji) -> Vec<(f64, f64)> {
+    let n = vzorec.len();
+    urejen(vzorec)
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| (d.kvantil(polozaji.p(i + 1, n)), x))
+        .collect()
+}
+
+// pari (p_i, F(x_(i))) na [0, 1] x [0, 1]
+pub fn pp_tocke<D: Porazdelitev + ?Sized>(
+    vzorec: &[f64],
+    d: &D,
+    polozaji: Polozaji,
+) -> Vec<(f64, f64)> {
+    let n = vzorec.len();
+    urejen(vzorec)
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| (polozaji.p(i + 1, n), d.cdf(x)))
+        .collect()
+}
+
+// polovična širina pasu zaupanja F_n ± ε iz KS testa, P(sup |F_n - F| > ε) ≈ alfa
+pub fn ks_pas(n: usize, alfa: f64) -> f64 {
+    assert!(n > 0 && 0.0 < alfa && alfa < 1.0);
+    // kolmogorov_sf pada, λ poiščemo z bisekcijo
+    let (mut lo, mut hi) = (0.2, 5.0);
+    for _ in 0..60 {
+        let sredina = 0.5 * (lo + hi);
+        if kolmogorov_sf(sredina) > alfa {
+            lo = sredina;
+        } else {
+            hi = sredina;
+        }
+    }
+    let koren = (n as f64).sqrt();
+    0.5 * (lo + hi) / (koren + 0.12 + 0.11 / koren)
+}
+
+// pas okoli premice y = x v grafu Q-Q: (x, Q(F(x) - ε), Q(F(x) + ε));
+// zunaj nosilca sta meji neskončni
+pub fn qq_pas<D: Kvantil + ?Sized>(d: &D, x: &[f64], eps: f64) -> Vec<(f64, f64, f64)> {
+    x.iter()
+        .map(|&x| {
+            let f = d.cdf(x);
+            let spodnja = if f - eps > 0.0 {
+                d.kvantil(f - eps)
+            } else {
+                f64::NEG_INFINITY
+            };
+            let zgornja = if f + eps < 1.0 {
+                d.kvantil(f + eps)
+            } else {
+                f64::INFINITY
+            };
+            (x, spodnja, zgornja)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qq_pp() {
+        assert_eq!(Polozaji::Blom.p(1, 1), 0.5);
+        assert_eq!(Polozaji::Hazen.p(1, 10), 0.05);
+        assert_eq!(polozaji(3, Polozaji::Weibull), vec![0.25, 0.5, 0.75]);
+        // kritična vrednost KS pri α = 0.05 je 1.358 / sqrt(n)
+        assert!((ks_pas(1_000_000, 0.05) * 1000.0 - 1.358).abs() < 1e-3);
+
+        let n = Normalna::new(1.0, 4.0);
+        let vzorec = n.vzorci(&mut Generator::new(5), 400);
+        let eps = ks_pas(vzorec.len(), 0.01);
+        let pp = pp_tocke(&vzorec, &n, Polozaji::Hazen);
+        assert!(pp.iter().all(|(p, f)| (p - f).abs() < eps));
+
+        let qq = qq_tocke(&vzorec, &n, Polozaji::Blom);
+        assert!(qq.windows(2).all(|t| t[0].0 < t[1].0 && t[0].1 <= t[1].1));
+        let pas = qq_pas(&n, &[1.0], eps);
+        assert!(pas[0].1 < 1.0 && pas[0].2 > 1.0);
+        assert!((pas[0].1 + pas[0].2 - 2.0).abs() < 1e-9);
+    }
+}
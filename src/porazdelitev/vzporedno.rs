@@ -0,0 +1,77 @@
+use std::thread;
+
+use crate::porazdelitev::*;
+
+// pod to velikostjo se niti ne splačajo
+const NAJMANJSI_KOS: usize = 4096;
+
+// x in izhod razdeli na enake kose in vsakega obdela v svoji niti
+fn razdeli<T, F>(x: &[T], izhod: &mut [f64], f: F)
+where
+    T: Sync,
+    F: Fn(&[T], &mut [f64]) + Sync,
+{
+    assert_eq!(x.len(), izhod.len());
+    let niti = thread::available_parallelism().map_or(1, |n| n.get());
+    let kos = x.len().div_ceil(niti).max(NAJMANJSI_KOS);
+    if kos >= x.len() {
+        return f(x, izhod);
+    }
+
+    thread::scope(|s| {
+        for (x, izhod) in x.chunks(kos).zip(izhod.chunks_mut(kos)) {
+            let f = &f;
+            s.spawn(move || f(x, izhod));
+        }
+    });
+}
+
+pub fn pdf_vzporedno<D: Zvezna + Sync + ?Sized>(d: &D, x: &[f64], izhod: &mut [f64]) {
+    razdeli(x, izhod, |x, izhod| d.pdf_batch(x, izhod));
+}
+
+pub fn cdf_vzporedno<D: Porazdelitev + Sync + ?Sized>(d: &D, x: &[f64], izhod: &mut [f64]) {
+    razdeli(x, izhod, |x, izhod| d.cdf_batch(x, izhod));
+}
+
+pub fn pmf_vzporedno<D: Diskretna + Sync + ?Sized>(d: &D, x: &[u64], izhod: &mut [f64]) {
+    razdeli(x, izhod, |x, izhod| d.pmf_batch(x, izhod));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // več kosov, kot je niti, in zadnji kos krajši
+    const N: usize = 10 * NAJMANJSI_KOS + 123;
+
+    #[test]
+    fn test_enako_kot_zaporedno() {
+        let x: Vec<f64> = (0..N).map(|i| i as f64 / 1000.0 - 5.0).collect();
+        let mut zaporedno = vec![0.0; N];
+        let mut vzporedno = vec![0.0; N];
+
+        let n = Normalna::new(1.0, 2.0);
+        n.pdf_batch(&x, &mut zaporedno);
+        pdf_vzporedno(&n, &x, &mut vzporedno);
+        assert_eq!(zaporedno, vzporedno);
+        n.cdf_batch(&x, &mut zaporedno);
+        cdf_vzporedno(&n, &x, &mut vzporedno);
+        assert_eq!(zaporedno, vzporedno);
+
+        // diskretne cdf računajo iz tabele delnih vsot, ki je v vsakem kosu drugačne dolžine
+        let p = Poisson::new(20.0);
+        p.cdf_batch(&x, &mut zaporedno);
+        cdf_vzporedno(&p, &x, &mut vzporedno);
+        assert_eq!(zaporedno, vzporedno);
+
+        let k: Vec<u64> = (0..N as u64).map(|i| i % 60).collect();
+        let b = Binomska::new(50, 0.3);
+        b.pmf_batch(&k, &mut zaporedno);
+        pmf_vzporedno(&b, &k, &mut vzporedno);
+        assert_eq!(zaporedno, vzporedno);
+        p.pmf_batch(&k, &mut zaporedno);
+        pmf_vzporedno(&p, &k, &mut vzporedno);
+        assert_eq!(zaporedno, vzporedno);
+    }
+}
@@ -0,0 +1,370 @@
+use core::fmt;
+
+use crate::porazdelitev::register;
+use crate::porazdelitev::*;
+
+// napaka pri razčlenjevanju, polozaj je indeks znaka (ne bajta) v vhodnem nizu
+#[derive(Debug, PartialEq, Clone)]
+pub struct NapakaRazclenjevanja {
+    pub polozaj: usize,
+    pub sporocilo: String,
+}
+
+impl NapakaRazclenjevanja {
+    // vhodni niz in pod njim puščica na mestu napake
+    pub fn oznaci(&self, niz: &str) -> String {
+        format!("{}\n{}^ {}", niz, " ".repeat(self.polozaj), self.sporocilo)
+    }
+}
+
+impl fmt::Display for NapakaRazclenjevanja {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (znak {})", self.sporocilo, self.polozaj + 1)
+    }
+}
+
+impl core::error::Error for NapakaRazclenjevanja {}
+
+// razčleni zapis, npr. "Binomska(n=20, p=0.3)" ali "Normal(0, 2)"; prebere porazdelitve iz
+// registra, katerih izpis (Display) je v enaki obliki. Sestavljene porazdelitve (Okrnjena,
+// Mesanica, Vsota, Transformirana) in porazdelitve iz podatkov se izpišejo podobno, a jih
+// razčlenjevalnik ne prebere, saj niso DynPorazdelitev
+pub fn razcleni(niz: &str) -> Result<Box<dyn DynPorazdelitev>, NapakaRazclenjevanja> {
+    Razclenjevalnik {
+        znaki: niz.chars().collect(),
+        i: 0,
+    }
+    .porazdelitev()
+}
+
+// ime argumenta (če je podano) s položajem, položaj vrednosti in vrednost
+struct Argument {
+    ime: Option<(usize, String)>,
+    polozaj: usize,
+    vrednost: f64,
+}
+
+struct Razclenjevalnik {
+    znaki: Vec<char>,
+    i: usize,
+}
+
+impl Razclenjevalnik {
+    fn napaka<T>(&self, polozaj: usize, sporocilo: String) -> Result<T, NapakaRazclenjevanja> {
+        Err(NapakaRazclenjevanja { polozaj, sporocilo })
+    }
+
+    fn presledki(&mut self) {
+        while self.i < self.znaki.len() && self.znaki[self.i].is_whitespace() {
+            self.i += 1;
+        }
+    }
+
+    fn trenutni(&self) -> Option<char> {
+        self.znaki.get(self.i).copied()
+    }
+
+    fn pricakuj(&mut self, c: char) -> Result<(), NapakaRazclenjevanja> {
+        self.presledki();
+        match self.trenutni() {
+            Some(d) if d == c => {
+                self.i += 1;
+                Ok(())
+            }
+            Some(d) => self.napaka(self.i, format!("pričakovan '{}', najden '{}'", c, d)),
+            None => self.napaka(self.i, format!("pričakovan '{}', niza je konec", c)),
+        }
+    }
+
+    fn identifikator(&mut self) -> Result<(usize, String), NapakaRazclenjevanja> {
+        self.presledki();
+        let zacetek = self.i;
+        while let Some(c) = self.trenutni() {
+            if c.is_alphanumeric() || c == '_' {
+                self.i += 1;
+            } else {
+                break;
+            }
+        }
+        if self.i == zacetek {
+            return self.napaka(zacetek, "pričakovano ime".to_string());
+        }
+
+        Ok((zacetek, self.znaki[zacetek..self.i].iter().collect()))
+    }
+
+    fn stevilo(&mut self) -> Result<(usize, f64), NapakaRazclenjevanja> {
+        self.presledki();
+        let zacetek = self.i;
+        while let Some(c) = self.trenutni() {
+            let eksponent = self.i > zacetek && matches!(self.znaki[self.i - 1], 'e' | 'E');
+            if c.is_ascii_digit()
+                || c == '.'
+                || c == 'e'
+                || c == 'E'
+                || ((c == '-' || c == '+') && (self.i == zacetek || eksponent))
+            {
+                self.i += 1;
+            } else {
+                break;
+            }
+        }
+        let besedilo: String = self.znaki[zacetek..self.i].iter().collect();
+        match besedilo.parse::<f64>() {
+            Ok(x) => Ok((zacetek, x)),
+            Err(_) if besedilo.is_empty() => {
+                self.napaka(zacetek, "pričakovano število".to_string())
+            }
+            Err(_) => self.napaka(zacetek, format!("neveljavno število '{}'", besedilo)),
+        }
+    }
+
+    // argument je "število" ali "ime = število"
+    fn argument(&mut self) -> Result<Argument, NapakaRazclenjevanja> {
+        self.presledki();
+        let mut ime = None;
+        if self.trenutni().is_some_and(|c| c.is_alphabetic()) {
+            ime = Some(self.identifikator()?);
+            self.pricakuj('=')?;
+        }
+        let (polozaj, vrednost) = self.stevilo()?;
+
+        Ok(Argument {
+            ime,
+            polozaj,
+            vrednost,
+        })
+    }
+
+    fn porazdelitev(&mut self) -> Result<Box<dyn DynPorazdelitev>, NapakaRazclenjevanja> {
+        let (polozaj_imena, ime) = self.identifikator()?;
+        let Some(vnos) = register::poisci(&ime) else {
+            return self.napaka(polozaj_imena, format!("neznana porazdelitev '{}'", ime));
+        };
+        self.pricakuj('(')?;
+        let oklepaj = self.i - 1;
+
+        let n = vnos.parametri.len();
+        let mut vrednosti: Vec<Option<f64>> = vec![None; n];
+        let mut polozaji = vec![oklepaj; n];
+        let mut poimenovani = false;
+        let mut pozicija = 0;
+
+        self.presledki();
+        if self.trenutni() != Some(')') {
+            loop {
+                let Argument {
+                    ime,
+                    polozaj,
+                    vrednost: x,
+                } = self.argument()?;
+                let k = match ime {
+                    Some((polozaj_imena, ime)) => {
+                        poimenovani = true;
+                        match vnos.parametri.iter().position(|p| p.ime == ime) {
+                            Some(k) => k,
+                            None => {
+                                return self.napaka(
+                                    polozaj_imena,
+                                    format!("{} nima parametra '{}'", vnos.ime, ime),
+                                );
+                            }
+                        }
+                    }
+                    None => {
+                        if poimenovani {
+                            return self
+                                .napaka(polozaj, "neimenovan parameter za imenovanim".to_string());
+                        }
+                        if pozicija >= n {
+                            return self
+                                .napaka(polozaj, format!("{} ima le {} parametrov", vnos.ime, n));
+                        }
+                        pozicija += 1;
+                        pozicija - 1
+                    }
+                };
+                let parameter = &vnos.parametri[k];
+                if vrednosti[k].is_some() {
+                    return self.napaka(
+                        polozaj,
+                        format!("parameter {} je podan dvakrat", parameter.ime),
+                    );
+                }
+                if parameter.celo_stevilo && x.fract() != 0.0 {
+                    return self
+                        .napaka(polozaj, format!("{} mora biti celo število", parameter.ime));
+                }
+                vrednosti[k] = Some(x);
+                polozaji[k] = polozaj;
+
+                self.presledki();
+                if self.trenutni() == Some(',') {
+                    self.i += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+        self.pricakuj(')')?;
+        let zaklepaj = self.i - 1;
+        self.presledki();
+        if self.i < self.znaki.len() {
+            return self.napaka(self.i, "odvečni znaki za zapisom".to_string());
+        }
+
+        let mut parametri = Vec::with_capacity(n);
+        for (p, x) in vnos.parametri.iter().zip(vrednosti) {
+            match x {
+                Some(x) => parametri.push(x),
+                None => return self.napaka(zaklepaj, format!("manjka parameter {}", p.ime)),
+            }
+        }
+
+        (vnos.ustvari)(&parametri).or_else(|e| {
+            // napačen je prvi argument, ob čigar zamenjavi s privzeto vrednostjo konstruktor uspe
+            let k = (0..n).find(|&k| {
+                let mut poskus = parametri.clone();
+                poskus[k] = vnos.parametri[k].privzeto;
+                (vnos.ustvari)(&poskus).is_ok()
+            });
+            self.napaka(k.map_or(oklepaj, |k| polozaji[k]), e)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_razcleni() {
+        let d = razcleni("Binomska(n=20, p=0.3)").unwrap();
+        assert_eq!(d.ime(), "Binomska");
+        assert_eq!(d.parametri(), vec![20.0, 0.3]);
+
+        let d = razcleni(" normal ( 0, 2 ) ").unwrap();
+        assert_eq!(d.parametri(), vec![0.0, 2.0]);
+
+        let d = razcleni("Hypergeometric(5, n=20, r=3)").unwrap();
+        assert_eq!(d.parametri(), vec![5.0, 3.0, 20.0]);
+
+        let d = razcleni("Exp(lambda=1.5e-1)").unwrap();
+        assert_eq!(d.parametri(), vec![0.15]);
+    }
+
+    #[test]
+    fn test_napake() {
+        let polozaj = |niz| razcleni(niz).err().unwrap().polozaj;
+        assert_eq!(polozaj("Cauchy(0, 1)"), 0);
+        assert_eq!(polozaj("Binomska(n=20, q=0.3)"), 15);
+        assert_eq!(polozaj("Binomska(n=20.5, p=0.3)"), 11);
+        assert_eq!(polozaj("Normalna(0 1)"), 11);
+        assert_eq!(polozaj("Normalna(0, 1) x"), 15);
+        assert_eq!(polozaj("Poisson()"), 8);
+        assert_eq!(polozaj("Poisson(1, 2)"), 11);
+        assert_eq!(polozaj("Poisson(-1)"), 8);
+        assert_eq!(polozaj("Binomska(n=5, p=1.5)"), 16);
+        assert_eq!(polozaj("Binomska(p=1.5, n=5)"), 11);
+        assert_eq!(polozaj("Hipergeometrijska(n=20, r=3, s=30)"), 31);
+        assert_eq!(polozaj("Poisson(1..2)"), 8);
+    }
+
+    #[test]
+    fn test_povratni_zapis() {
+        for niz in [
+            "Bernoulli(p=0.1)",
+            "Binomska(n=7, p=0.3333333333333333)",
+            "Eksponentna(lambda=2.5)",
+            "EnakomernaInterval(a=-1, b=0.1)",
+            "Geometrijska(p=0.2)",
+            "Hipergeometrijska(s=5, r=3, n=20)",
+            "Normalna(mu=0.1, sigma2=1e-7)",
+            "Poisson(lambda=3)",
+            "StudentovaT(n=2.5)",
+            "HiKvadrat(k=7)",
+        ] {
+            let d = razcleni(niz).unwrap();
+            let zapis = d.to_string();
+            let e = razcleni(&zapis).unwrap();
+            assert_eq!(d.ime(), e.ime());
+            assert_eq!(d.parametri(), e.parametri());
+        }
+        // vse porazdelitve iz registra, tudi z robnimi vrednostmi parametrov
+        for vnos in register::REGISTER.iter() {
+            let privzeti = vnos.privzeti_parametri();
+            // prvi parameter na spodnji meji, ostali na zgornji, da velja a < b
+            let robni: Vec<f64> = vnos
+                .parametri
+                .iter()
+                .enumerate()
+                .map(|(i, p)| if i == 0 { p.razpon.0 } else { p.razpon.1 })
+                .collect();
+            for parametri in [privzeti, robni] {
+                let d = register::ustvari(vnos.ime, &parametri).unwrap();
+                let e = razcleni(&d.to_string()).unwrap();
+                assert_eq!(e.ime(), vnos.ime);
+                assert_eq!(e.parametri(), parametri);
+                assert_eq!(e.to_string(), d.to_string());
+            }
+        }
+        assert_eq!(Binomska::new(20, 0.3).to_string(), "Binomska(n=20, p=0.3)");
+        assert_eq!(
+            razcleni(&Normalna::new(1.0, 0.1).to_string())
+                .unwrap()
+                .parametri(),
+            vec![1.0, 0.1]
+        );
+    }
+
+    #[test]
+    fn test_zapis_sestavljenih() {
+        assert_eq!(
+            Empiricna::new(&[3.0, 1.0, 2.0]).to_string(),
+            "Empiricna(n=3)"
+        );
+        let j = JedrnaOcena::new(&[0.0, 1.0], Jedro::Gaussovo, SirinaPasu::Fiksna(0.5));
+        assert_eq!(j.to_string(), "JedrnaOcena(n=2, jedro=Gaussovo, h=0.5)");
+        assert_eq!(
+            KoncnaDiskretna::new(vec![1.0, 3.0]).to_string(),
+            "KoncnaDiskretna(0.25, 0.75)"
+        );
+        let m: Mesanica<dyn ZveznaKomponenta> = Mesanica::new(vec![
+            (1.0, Box::new(Normalna::new(0.0, 1.0))),
+            (3.0, Box::new(Eksponentna::new(2.0))),
+        ]);
+        assert_eq!(
+            m.to_string(),
+            "Mesanica(0.25 Normalna(mu=0, sigma2=1), 0.75 Eksponentna(lambda=2))"
+        );
+        let o = Okrnjena::new(Poisson::new(3.0), 1.0, f64::INFINITY);
+        assert_eq!(o.to_string(), "Okrnjena(Poisson(lambda=3), a=1, b=inf)");
+        let t = Transformirana::new(
+            Normalna::new(0.0, 1.0),
+            Preslikava::Afina { a: 2.0, b: 1.0 },
+        )
+        .unwrap();
+        assert_eq!(
+            t.to_string(),
+            "Transformirana(Normalna(mu=0, sigma2=1), 2x + 1)"
+        );
+        let v = Vsota::new(Eksponentna::new(1.0), EnakomernaInterval::new(0.0, 1.0));
+        assert_eq!(
+            v.to_string(),
+            "Vsota(Eksponentna(lambda=1), EnakomernaInterval(a=0, b=1))"
+        );
+
+        // sestavljenih razčlenjevalnik ne prebere, prebere pa njihove dele
+        for zapis in [
+            m.to_string(),
+            o.to_string(),
+            t.to_string(),
+            v.to_string(),
+            j.to_string(),
+        ] {
+            assert_eq!(razcleni(&zapis).err().unwrap().polozaj, 0);
+        }
+        let p = razcleni(&o.porazdelitev().to_string()).unwrap();
+        assert_eq!(p.parametri(), vec![3.0]);
+    }
+}
@@ -0,0 +1,6 @@
+// brez std: imena iz privzetega preludija std uvozimo iz alloc, matematične funkcije za f64 pa iz libm
+pub(crate) use crate::realno::Realno;
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec::Vec;
+pub(crate) use alloc::{format, vec};
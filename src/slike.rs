@@ -0,0 +1,371 @@
+use crate::porazdelitev::DynPorazdelitev;
+#[cfg(not(any(feature = "std", test)))]
+use crate::prelude::*;
+use core::fmt::Write;
+
+// slike porazdelitev v SVG (in PNG z možnostjo png) brez grafičnega vmesnika
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Vrsta {
+    // stolpci pmf pri diskretnih, črta pdf pri zveznih
+    Gostota,
+    // stopničasta pri diskretnih
+    Cdf,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Serija {
+    // navpični stolpci (x, višina)
+    Stolpci(Vec<(f64, f64)>),
+    // lomljena črta skozi točke
+    Crta(Vec<(f64, f64)>),
+}
+
+impl Serija {
+    pub fn tocke(&self) -> &[(f64, f64)] {
+        match self {
+            Serija::Stolpci(tocke) | Serija::Crta(tocke) => tocke,
+        }
+    }
+}
+
+// barve zaporednih serij
+pub const BARVE: [&str; 6] = [
+    "#1f77b4", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#17becf",
+];
+
+#[derive(Debug, Clone)]
+pub struct Slika {
+    // v pikslih
+    pub sirina: u32,
+    pub visina: u32,
+    pub naslov: String,
+    pub oznaka_x: String,
+    pub oznaka_y: String,
+    // (serija, ime v legendi, barva)
+    pub serije: Vec<(Serija, String, String)>,
+}
+
+// robovi okoli območja grafa: levo, desno, zgoraj, spodaj
+const ROBOVI: (f64, f64, f64, f64) = (64.0, 24.0, 40.0, 52.0);
+
+impl Slika {
+    pub fn new(naslov: &str, oznaka_x: &str, oznaka_y: &str) -> Self {
+        Slika {
+            sirina: 640,
+            visina: 400,
+            naslov: naslov.to_string(),
+            oznaka_x: oznaka_x.to_string(),
+            oznaka_y: oznaka_y.to_string(),
+            serije: Vec::new(),
+        }
+    }
+
+    // doda serijo z naslednjo barvo iz BARVE
+    pub fn dodaj(&mut self, serija: Serija, ime: &str) {
+        let barva = BARVE[self.serije.len() % BARVE.len()];
+        self.serije
+            .push((serija, ime.to_string(), barva.to_string()));
+    }
+
+    // širina stolpcev je 0.6 najmanjšega razmika med sosednjima stolpcema
+    fn sirina_stolpcev(&self) -> f64 {
+        let mut razmik = f64::INFINITY;
+        for (serija, _, _) in &self.serije {
+            if let Serija::Stolpci(tocke) = serija {
+                for par in tocke.windows(2) {
+                    let d = (par[1].0 - par[0].0).abs();
+                    if d > 0.0 {
+                        razmik = razmik.min(d);
+                    }
+                }
+            }
+        }
+        0.6 * if razmik.is_finite() { razmik } else { 1.0 }
+    }
+
+    // [x_min, x_max] x [0, y_max] vseh serij, stolpci so vključeni v celoti
+    fn meje(&self) -> (f64, f64, f64, f64) {
+        let pol = 0.5 * self.sirina_stolpcev();
+        let (mut x0, mut x1, mut y1) = (f64::INFINITY, f64::NEG_INFINITY, 0.0f64);
+        for (serija, _, _) in &self.serije {
+            let rob = if matches!(serija, Serija::Stolpci(_)) {
+                pol
+            } else {
+                0.0
+            };
+            for &(x, y) in serija
+                .tocke()
+                .iter()
+                .filter(|(x, y)| x.is_finite() && y.is_finite())
+            {
+                x0 = x0.min(x - rob);
+                x1 = x1.max(x + rob);
+                y1 = y1.max(y);
+            }
+        }
+        if x0 > x1 {
+            (x0, x1) = (0.0, 1.0);
+        } else if x0 == x1 {
+            (x0, x1) = (x0 - 0.5, x1 + 0.5);
+        }
+        if y1 <= 0.0 {
+            y1 = 1.0;
+        }
+        (x0, x1, 0.0, 1.05 * y1)
+    }
+
+    pub fn svg(&self) -> String {
+        let (w, h) = (self.sirina as f64, self.visina as f64);
+        let (levo, desno, zgoraj, spodaj) = ROBOVI;
+        let (x0, x1, y0, y1) = self.meje();
+        let px = |x: f64| levo + (x - x0) / (x1 - x0) * (w - levo - desno);
+        let py = |y: f64| h - spodaj - (y - y0) / (y1 - y0) * (h - zgoraj - spodaj);
+
+        let mut s = String::new();
+        // pisanje v String ne more spodleteti
+        let _ = writeln!(
+            s,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="DejaVu Sans, Liberation Sans, Arial, Helvetica, sans-serif" font-size="12">"#
+        );
+        let _ = writeln!(s, r#"<rect width="{w}" height="{h}" fill="white"/>"#);
+        let _ = writeln!(
+            s,
+            r#"<clipPath id="obmocje"><rect x="{levo}" y="{zgoraj}" width="{}" height="{}"/></clipPath>"#,
+            w - levo - desno,
+            h - zgoraj - spodaj
+        );
+        let _ = writeln!(
+            s,
+            r#"<text x="{}" y="24" text-anchor="middle" font-size="15">{}</text>"#,
+            w / 2.0,
+            ubezi(&self.naslov)
+        );
+
+        // mreža in oznake na oseh
+        for (x, oznaka) in oznake(x0, x1) {
+            let _ = writeln!(
+                s,
+                r##"<line x1="{0:.2}" y1="{zgoraj}" x2="{0:.2}" y2="{1}" stroke="#e0e0e0"/><text x="{0:.2}" y="{2}" text-anchor="middle">{oznaka}</text>"##,
+                px(x),
+                h - spodaj + 5.0,
+                h - spodaj + 18.0
+            );
+        }
+        for (y, oznaka) in oznake(y0, y1) {
+            let _ = writeln!(
+                s,
+                r##"<line x1="{0}" y1="{1:.2}" x2="{2}" y2="{1:.2}" stroke="#e0e0e0"/><text x="{3}" y="{4:.2}" text-anchor="end">{oznaka}</text>"##,
+                levo - 5.0,
+                py(y),
+                w - desno,
+                levo - 8.0,
+                py(y) + 4.0
+            );
+        }
+        let _ = writeln!(
+            s,
+            r#"<rect x="{levo}" y="{zgoraj}" width="{}" height="{}" fill="none" stroke="black"/>"#,
+            w - levo - desno,
+            h - zgoraj - spodaj
+        );
+        let _ = writeln!(
+            s,
+            r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+            levo + (w - levo - desno) / 2.0,
+            h - 12.0,
+            ubezi(&self.oznaka_x)
+        );
+        let sredina_y = zgoraj + (h - zgoraj - spodaj) / 2.0;
+        let _ = writeln!(
+            s,
+            r#"<text x="16" y="{sredina_y}" text-anchor="middle" transform="rotate(-90 16 {sredina_y})">{}</text>"#,
+            ubezi(&self.oznaka_y)
+        );
+
+        let _ = writeln!(s, r#"<g clip-path="url(#obmocje)">"#);
+        let pol = 0.5 * self.sirina_stolpcev();
+        for (serija, _, barva) in &self.serije {
+            let tocke = serija
+                .tocke()
+                .iter()
+                .filter(|(x, y)| x.is_finite() && y.is_finite());
+            match serija {
+                Serija::Stolpci(_) => {
+                    for &(x, y) in tocke {
+                        let _ = writeln!(
+                            s,
+                            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{barva}" fill-opacity="0.8"/>"#,
+                            px(x - pol),
+                            py(y),
+                            px(x + pol) - px(x - pol),
+                            py(0.0) - py(y)
+                        );
+                    }
+                }
+                Serija::Crta(_) => {
+                    let mut tocke_svg = String::new();
+                    for &(x, y) in tocke {
+                        let _ = write!(tocke_svg, "{:.2},{:.2} ", px(x), py(y));
+                    }
+                    let _ = writeln!(
+                        s,
+                        r#"<polyline points="{}" fill="none" stroke="{barva}" stroke-width="2"/>"#,
+                        tocke_svg.trim_end()
+                    );
+                }
+            }
+        }
+        let _ = writeln!(s, "</g>");
+
+        // legenda v desnem zgornjem kotu, le pri več serijah
+        if self.serije.len() > 1 {
+            for (i, (_, ime, barva)) in self.serije.iter().enumerate() {
+                let y = zgoraj + 10.0 + 18.0 * i as f64;
+                let _ = writeln!(
+                    s,
+                    r#"<rect x="{}" y="{}" width="12" height="12" fill="{barva}"/><text x="{}" y="{}" text-anchor="end">{}</text>"#,
+                    w - desno - 20.0,
+                    y,
+                    w - desno - 26.0,
+                    y + 10.0,
+                    ubezi(ime)
+                );
+            }
+        }
+        s += "</svg>\n";
+        s
+    }
+
+    // za besedilo potrebuje sistemske pisave; če jih ni, oznake manjkajo
+    #[cfg(feature = "png")]
+    pub fn png(&self) -> Result<Vec<u8>, String> {
+        use resvg::{tiny_skia, usvg};
+
+        let mut nastavitve = usvg::Options::default();
+        nastavitve.fontdb_mut().load_system_fonts();
+        let drevo = usvg::Tree::from_str(&self.svg(), &nastavitve).map_err(|e| e.to_string())?;
+        let mut slika = tiny_skia::Pixmap::new(self.sirina, self.visina)
+            .ok_or_else(|| "neveljavna velikost slike".to_string())?;
+        resvg::render(&drevo, tiny_skia::Transform::default(), &mut slika.as_mut());
+        slika.encode_png().map_err(|e| e.to_string())
+    }
+}
+
+// znaki, ki imajo v XML poseben pomen
+fn ubezi(besedilo: &str) -> String {
+    besedilo
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// okrogle vrednosti s korakom 1, 2 ali 5 krat potenca 10, od 4 do 8 na intervalu
+pub fn oznake(od: f64, do_: f64) -> Vec<(f64, String)> {
+    assert!(od < do_);
+    let surovi = (do_ - od) / 8.0;
+    let red = (surovi.ln() / core::f64::consts::LN_10).floor();
+    let potenca = 10f64.powi(red as i32);
+    let korak = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|k| k * potenca)
+        .find(|&k| k >= surovi)
+        .unwrap_or(10.0 * potenca);
+    let decimalke = (-(korak.ln() / core::f64::consts::LN_10 + 1e-9).floor()).max(0.0) as usize;
+
+    let mut oznake = Vec::new();
+    let mut i = (od / korak).ceil();
+    while i * korak <= do_ + 1e-9 * korak {
+        let x = i * korak;
+        // -0 izpišemo kot 0
+        let x = if x == 0.0 { 0.0 } else { x };
+        oznake.push((x, format!("{:.*}", decimalke, x)));
+        i += 1.0;
+    }
+    oznake
+}
+
+// gostota ali cdf na istem območju kot v grafičnem vmesniku
+pub fn serija(d: &dyn DynPorazdelitev, vrsta: Vrsta) -> Serija {
+    if d.diskretna() {
+        // pri zelo širokih nosilcih omejimo število stolpcev
+        let od = d.kvantil(0.0005);
+        let do_ = d.kvantil(0.9995).min(od + 1000.0);
+        let k: Vec<f64> = (od as u64..=do_ as u64).map(|k| k as f64).collect();
+        match vrsta {
+            Vrsta::Gostota => Serija::Stolpci(k.iter().map(|&k| (k, d.gostota(k))).collect()),
+            Vrsta::Cdf => {
+                let mut stopnice = vec![(od - 1.0, d.cdf(od - 1.0))];
+                for &k in &k {
+                    stopnice.push((k, stopnice[stopnice.len() - 1].1));
+                    stopnice.push((k, d.cdf(k)));
+                }
+                stopnice.push((do_ + 1.0, d.cdf(do_)));
+                Serija::Crta(stopnice)
+            }
+        }
+    } else {
+        let (lo, hi) = (d.kvantil(0.001), d.kvantil(0.999));
+        let rob = (hi - lo) * 0.1;
+        let x: Vec<f64> = (0..=500)
+            .map(|i| lo - rob + (hi - lo + 2.0 * rob) * i as f64 / 500.0)
+            .collect();
+        let mut y = vec![0.0; x.len()];
+        match vrsta {
+            Vrsta::Gostota => d.gostota_batch(&x, &mut y),
+            Vrsta::Cdf => d.cdf_batch(&x, &mut y),
+        }
+        Serija::Crta(x.into_iter().zip(y).collect())
+    }
+}
+
+// slika ene porazdelitve z imenom v naslovu
+pub fn slika_porazdelitve(d: &dyn DynPorazdelitev, vrsta: Vrsta) -> Slika {
+    let oznaka_y = match (vrsta, d.diskretna()) {
+        (Vrsta::Gostota, true) => "P(X = x)",
+        (Vrsta::Gostota, false) => "f(x)",
+        (Vrsta::Cdf, _) => "F(x)",
+    };
+    let ime = d.to_string();
+    let mut slika = Slika::new(&ime, "x", oznaka_y);
+    slika.dodaj(serija(d, vrsta), &ime);
+    slika
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Binomska, Normalna};
+
+    #[test]
+    fn test_oznake() {
+        let x: Vec<f64> = oznake(0.0, 1.05).iter().map(|o| o.0).collect();
+        assert_eq!(x.len(), 6);
+        assert_eq!(oznake(0.0, 1.05)[1].1, "0.2");
+        assert_eq!(oznake(-3.2, 7.9)[0], (-2.0, "-2".to_string()));
+        assert_eq!(oznake(0.0, 0.042)[1].1, "0.01");
+    }
+
+    #[test]
+    fn test_svg() {
+        let b = Binomska::new(10, 0.3);
+        let slika = slika_porazdelitve(&b, Vrsta::Gostota);
+        let svg = slika.svg();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        // ozadje, okvir, obrezovalni pravokotnik in stolpci
+        let stolpci = slika.serije[0].0.tocke().len();
+        assert_eq!(svg.matches("<rect").count(), 3 + stolpci);
+        assert!(svg.contains("Binomska"));
+
+        let n = Normalna::new(0.0, 1.0);
+        let mut slika = slika_porazdelitve(&n, Vrsta::Cdf);
+        slika.dodaj(
+            serija(&Normalna::new(1.0, 1.0), Vrsta::Cdf),
+            "N(1, 1) <primerjava>",
+        );
+        let svg = slika.svg();
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(svg.contains("&lt;primerjava&gt;"));
+    }
+}
@@ -0,0 +1,286 @@
+use crate::porazdelitev::{HiKvadrat, Kvantil, Porazdelitev, StudentovaT};
+#[cfg(not(any(feature = "std", test)))]
+use crate::prelude::*;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Oblika {
+    Markdown,
+    Csv,
+    // okolje tabular
+    Latex,
+}
+
+// tabela števil z oznakami vrstic in stolpcev
+#[derive(Debug, Clone)]
+pub struct Tabela {
+    // oznaka v levem zgornjem kotu, npr. "z"
+    pub kot: String,
+    pub stolpci: Vec<String>,
+    pub vrstice: Vec<String>,
+    pub vrednosti: Vec<Vec<f64>>,
+}
+
+impl Tabela {
+    pub fn izpisi(&self, oblika: Oblika, decimalke: usize) -> String {
+        let celice = |i: usize| -> Vec<String> {
+            self.vrednosti[i]
+                .iter()
+                .map(|x| format!("{:.*}", decimalke, x))
+                .collect()
+        };
+
+        let mut izpis = String::new();
+        match oblika {
+            Oblika::Markdown => {
+                izpis += &format!("| {} | {} |\n", self.kot, self.stolpci.join(" | "));
+                izpis += &format!("|---|{}\n", "---:|".repeat(self.stolpci.len()));
+                for (i, vrstica) in self.vrstice.iter().enumerate() {
+                    izpis += &format!("| {} | {} |\n", vrstica, celice(i).join(" | "));
+                }
+            }
+            Oblika::Csv => {
+                let stolpci: Vec<String> = self.stolpci.iter().map(|s| csv_polje(s)).collect();
+                izpis += &format!("{},{}\n", csv_polje(&self.kot), stolpci.join(","));
+                for (i, vrstica) in self.vrstice.iter().enumerate() {
+                    izpis += &format!("{},{}\n", csv_polje(vrstica), celice(i).join(","));
+                }
+            }
+            Oblika::Latex => {
+                izpis += &format!(
+                    "\\begin{{tabular}}{{c|{}}}\n",
+                    "r".repeat(self.stolpci.len())
+                );
+                let stolpci: Vec<String> = self.stolpci.iter().map(|s| latex_besedilo(s)).collect();
+                izpis += &format!(
+                    "${}$ & {} \\\\\n\\hline\n",
+                    latex_besedilo(&self.kot),
+                    stolpci.join(" & ")
+                );
+                for (i, vrstica) in self.vrstice.iter().enumerate() {
+                    izpis += &format!(
+                        "{} & {} \\\\\n",
+                        latex_besedilo(vrstica),
+                        celice(i).join(" & ")
+                    );
+                }
+                izpis += "\\end{tabular}\n";
+            }
+        }
+        izpis
+    }
+}
+
+// oznake, kot je "Normal(0, 1)", vsebujejo vejice
+fn csv_polje(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// posebni znaki LaTeXa v oznakah, npr. "Poisson(lambda=2)" ali "p_1"
+fn latex_besedilo(s: &str) -> String {
+    let mut izhod = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                izhod.push('\\');
+                izhod.push(c);
+            }
+            '\\' => izhod += "\\textbackslash{}",
+            '~' => izhod += "\\textasciitilde{}",
+            '^' => izhod += "\\textasciicircum{}",
+            _ => izhod.push(c),
+        }
+    }
+    izhod
+}
+
+// od, od + korak, ..., do vključno do_; zaokroženo, da se izognemo 0.30000000000000004
+pub fn razpon(od: f64, do_: f64, korak: f64) -> Vec<f64> {
+    assert!(korak > 0.0 && od <= do_);
+    let n = ((do_ - od) / korak + 1e-9).floor() as usize;
+    (0..=n)
+        .map(|i| ((od + i as f64 * korak) * 1e10).round() / 1e10)
+        .collect()
+}
+
+// oznake z enakim številom decimalk, npr. 0.00, 0.01, ..., 0.09
+fn oznake(xs: &[f64]) -> Vec<String> {
+    let decimalke = xs
+        .iter()
+        .map(|x| x.to_string().split('.').nth(1).map_or(0, |d| d.len()))
+        .max()
+        .unwrap_or(0);
+    xs.iter().map(|x| format!("{:.*}", decimalke, x)).collect()
+}
+
+// klasična tabela F(x), kjer je x = vrstica + stolpec, npr. Φ(z) s stolpci za stotinke;
+// v negativnih vrsticah stolpec odštejemo, kot je običajno v tabelah
+pub fn mreza_cdf<D: Porazdelitev + ?Sized>(
+    d: &D,
+    kot: &str,
+    vrstice: &[f64],
+    stolpci: &[f64],
+) -> Tabela {
+    let vrednosti = vrstice
+        .iter()
+        .map(|&v| {
+            stolpci
+                .iter()
+                .map(|&s| d.cdf(if v.is_sign_negative() { v - s } else { v + s }))
+                .collect()
+        })
+        .collect();
+
+    Tabela {
+        kot: kot.to_string(),
+        stolpci: oznake(stolpci),
+        vrstice: oznake(vrstice),
+        vrednosti,
+    }
+}
+
+// tabela Φ(z) za z od 0.0 do 3.9
+pub fn tabela_normalne() -> Tabela {
+    let n = crate::Normalna::new(0.0, 1.0);
+    mreza_cdf(&n, "z", &razpon(0.0, 3.9, 0.1), &razpon(0.0, 0.09, 0.01))
+}
+
+// vrstica za vsako porazdelitev, stolpec za vsako verjetnost p, v celici je kvantil
+pub fn tabela_kvantilov(porazdelitve: &[(String, &dyn Kvantil)], verjetnosti: &[f64]) -> Tabela {
+    Tabela {
+        kot: "p".to_string(),
+        stolpci: oznake(verjetnosti),
+        vrstice: porazdelitve.iter().map(|(ime, _)| ime.clone()).collect(),
+        vrednosti: porazdelitve
+            .iter()
+            .map(|(_, d)| verjetnosti.iter().map(|&p| d.kvantil(p)).collect())
+            .collect(),
+    }
+}
+
+// kritične vrednosti, vrstica za vsako število prostostnih stopenj in stolpec za vsako
+// p = P(T <= t); zgornji rep α ustreza p = 1 - α
+pub fn tabela_studentove(stopnje: &[f64], verjetnosti: &[f64]) -> Tabela {
+    tabela_stopenj("n", stopnje, verjetnosti, StudentovaT::new)
+}
+
+// kot tabela_studentove, v celici je x s P(χ²_k <= x) = p
+pub fn tabela_hi_kvadrat(stopnje: &[f64], verjetnosti: &[f64]) -> Tabela {
+    tabela_stopenj("k", stopnje, verjetnosti, HiKvadrat::new)
+}
+
+fn tabela_stopenj<D: Kvantil>(
+    kot: &str,
+    stopnje: &[f64],
+    verjetnosti: &[f64],
+    ustvari: fn(f64) -> D,
+) -> Tabela {
+    Tabela {
+        kot: kot.to_string(),
+        stolpci: oznake(verjetnosti),
+        vrstice: oznake(stopnje),
+        vrednosti: stopnje
+            .iter()
+            .map(|&n| {
+                let d = ustvari(n);
+                verjetnosti.iter().map(|&p| d.kvantil(p)).collect()
+            })
+            .collect(),
+    }
+}
+
+// P(X <= k) za k = 0, ..., k_max, stolpec za vsako porazdelitev (npr. Poissonova za več λ)
+pub fn kumulativna_tabela(porazdelitve: &[(String, &dyn Porazdelitev)], k_max: u64) -> Tabela {
+    Tabela {
+        kot: "k".to_string(),
+        stolpci: porazdelitve.iter().map(|(ime, _)| ime.clone()).collect(),
+        vrstice: (0..=k_max).map(|k| k.to_string()).collect(),
+        vrednosti: (0..=k_max)
+            .map(|k| porazdelitve.iter().map(|(_, d)| d.cdf(k as f64)).collect())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Poisson;
+    use crate::porazdelitev::Zvezna;
+
+    #[test]
+    fn test_tabela_normalne() {
+        let t = tabela_normalne();
+        assert_eq!(t.vrstice.len(), 40);
+        assert_eq!(t.stolpci[3], "0.03");
+        assert_eq!(t.vrstice[12], "1.2");
+        // Φ(1.23) = 0.8907
+        assert_eq!(format!("{:.4}", t.vrednosti[12][3]), "0.8907");
+
+        let md = t.izpisi(Oblika::Markdown, 4);
+        assert!(md.starts_with("| z | 0.00 | 0.01 |"));
+        assert!(md.contains("| 1.9 | 0.9713 | 0.9719 |"));
+        let tex = t.izpisi(Oblika::Latex, 3);
+        assert!(tex.contains("1.9 & 0.971 & 0.972 &"));
+        assert!(tex.ends_with("\\end{tabular}\n"));
+    }
+
+    #[test]
+    fn test_kumulativna() {
+        let p1 = Poisson::new(1.0);
+        let p2 = Poisson::new(2.0);
+        let t = kumulativna_tabela(&[("1".to_string(), &p1), ("2".to_string(), &p2)], 3);
+        let csv = t.izpisi(Oblika::Csv, 4);
+        assert_eq!(csv.lines().next().unwrap(), "k,1,2");
+        assert_eq!(csv.lines().nth(1).unwrap(), "0,0.3679,0.1353");
+    }
+
+    #[test]
+    fn test_studentova_hi_kvadrat() {
+        // klasične tabele kritičnih vrednosti
+        let t = tabela_studentove(&[1.0, 5.0, 30.0], &[0.95, 0.975, 0.995]);
+        assert_eq!(t.kot, "n");
+        assert_eq!(t.vrstice, vec!["1", "5", "30"]);
+        let pricakovane = [
+            [6.314, 12.706, 63.657],
+            [2.015, 2.571, 4.032],
+            [1.697, 2.042, 2.750],
+        ];
+        for (vrstica, pricakovana) in t.vrednosti.iter().zip(pricakovane) {
+            for (x, y) in vrstica.iter().zip(pricakovana) {
+                assert_eq!(format!("{:.3}", x), format!("{:.3}", y));
+            }
+        }
+        let t = StudentovaT::new(7.0);
+        assert!((t.kvantil(0.1) + t.kvantil(0.9)).abs() < 1e-12);
+        assert!((t.cdf(t.kvantil(0.3)) - 0.3).abs() < 1e-12);
+        let masa = crate::utils::integriraj(|x| t.pdf(x), -1.0, 2.0);
+        assert!((masa - (t.cdf(2.0) - t.cdf(-1.0))).abs() < 1e-9);
+
+        let h = tabela_hi_kvadrat(&[1.0, 10.0], &[0.05, 0.95]);
+        assert_eq!(format!("{:.4}", h.vrednosti[0][0]), "0.0039");
+        assert_eq!(format!("{:.3}", h.vrednosti[0][1]), "3.841");
+        assert_eq!(format!("{:.3}", h.vrednosti[1][0]), "3.940");
+        assert_eq!(format!("{:.3}", h.vrednosti[1][1]), "18.307");
+        let h = HiKvadrat::new(4.0);
+        assert!((h.sf(h.isf(1e-10)) / 1e-10 - 1.0).abs() < 1e-9);
+        let masa = crate::utils::integriraj(|x| h.pdf(x), 0.5, 6.0);
+        assert!((masa - (h.cdf(6.0) - h.cdf(0.5))).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_latex() {
+        let t = Tabela {
+            kot: "x_1".to_string(),
+            stolpci: vec!["50%".to_string()],
+            vrstice: vec!["A & B".to_string()],
+            vrednosti: vec![vec![0.5]],
+        };
+        assert_eq!(
+            t.izpisi(Oblika::Latex, 1),
+            "\\begin{tabular}{c|r}\n$x\\_1$ & 50\\% \\\\\n\\hline\nA \\& B & 0.5 \\\\\n\\end{tabular}\n"
+        );
+    }
+}
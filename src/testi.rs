@@ -0,0 +1,149 @@
+use crate::porazdelitev::{DynPorazdelitev, Empiricna, Porazdelitev};
+#[cfg(not(any(feature = "std", test)))]
+use crate::prelude::*;
+use crate::utils;
+
+// testi prileganja vzorca porazdelitvi
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RezultatTesta {
+    pub statistika: f64,
+    pub p_vrednost: f64,
+    // pri testu χ² število prostostnih stopenj, pri KS velikost vzorca
+    pub stopnje: usize,
+}
+
+// P(χ²_k > x)
+pub fn hi_kvadrat_sf(x: f64, k: usize) -> f64 {
+    assert!(k > 0);
+    utils::gama_q(0.5 * k as f64, 0.5 * x.max(0.0))
+}
+
+// P(K > lambda) za Kolmogorovo porazdelitev, 2 sum (-1)^(j-1) exp(-2 j² λ²)
+pub fn kolmogorov_sf(lambda: f64) -> f64 {
+    let mut vsota = 0.0;
+    let mut predznak = 2.0;
+    let mut prejsnji = 0.0f64;
+    for j in 1..=100 {
+        let clen = predznak * (-2.0 * (j * j) as f64 * lambda * lambda).exp();
+        vsota += clen;
+        if clen.abs() <= 1e-3 * prejsnji || clen.abs() <= 1e-8 * vsota {
+            return vsota.clamp(0.0, 1.0);
+        }
+        predznak = -predznak;
+        prejsnji = clen.abs();
+    }
+    // vrsta pri zelo majhnih λ ne konvergira, tam je p-vrednost 1
+    1.0
+}
+
+// Kolmogorov-Smirnovov test za zvezno porazdelitev, p-vrednost po asimptotski
+// porazdelitvi s popravkom za majhne n (Stephens); pri diskretnih je test konzervativen
+pub fn ks_test<D: Porazdelitev + ?Sized>(vzorec: &[f64], d: &D) -> RezultatTesta {
+    let e = Empiricna::new(vzorec);
+    let statistika = e.ks_statistika(d);
+    let n = (e.n() as f64).sqrt();
+    RezultatTesta {
+        statistika,
+        p_vrednost: kolmogorov_sf((n + 0.12 + 0.11 / n) * statistika),
+        stopnje: e.n(),
+    }
+}
+
+// test χ² na razredih (-inf, c_1], (c_1, c_2], ..., (c_m, inf), kjer so c_i meje;
+// sosednje razrede s pričakovano frekvenco pod 5 združimo
+pub fn hi_kvadrat_test<D: Porazdelitev + ?Sized>(
+    vzorec: &[f64],
+    d: &D,
+    meje: &[f64],
+    ocenjeni_parametri: usize,
+) -> RezultatTesta {
+    assert!(meje.windows(2).all(|m| m[0] < m[1]));
+    let n = vzorec.len() as f64;
+
+    let mut opazene = vec![0.0; meje.len() + 1];
+    for &x in vzorec {
+        opazene[meje.partition_point(|&c| c < x)] += 1.0;
+    }
+    let mut cdf: Vec<f64> = meje.iter().map(|&c| d.cdf(c)).collect();
+    cdf.insert(0, 0.0);
+    cdf.push(1.0);
+    let pricakovane: Vec<f64> = cdf.windows(2).map(|f| n * (f[1] - f[0])).collect();
+
+    let mut razredi: Vec<(f64, f64)> = Vec::new();
+    let (mut o, mut p) = (0.0, 0.0);
+    for (&oi, &pi) in opazene.iter().zip(&pricakovane) {
+        o += oi;
+        p += pi;
+        if p >= 5.0 {
+            razredi.push((o, p));
+            (o, p) = (0.0, 0.0);
+        }
+    }
+    // ostanek dodamo zadnjemu razredu
+    match razredi.last_mut() {
+        Some(zadnji) => {
+            zadnji.0 += o;
+            zadnji.1 += p;
+        }
+        None => razredi.push((o, p)),
+    }
+
+    let statistika = razredi
+        .iter()
+        .filter(|(_, p)| *p > 0.0)
+        .map(|(o, p)| (o - p) * (o - p) / p)
+        .sum();
+    let stopnje = razredi.len().saturating_sub(1 + ocenjeni_parametri).max(1);
+    RezultatTesta {
+        statistika,
+        p_vrednost: hi_kvadrat_sf(statistika, stopnje),
+        stopnje,
+    }
+}
+
+// χ² za diskretne porazdelitve (razred za vsako celo število do zanemarljivega repa), KS za zvezne
+pub fn test_prileganja(
+    vzorec: &[f64],
+    d: &dyn DynPorazdelitev,
+    ocenjeni_parametri: usize,
+) -> RezultatTesta {
+    if d.diskretna() {
+        let od = d.kvantil(0.0005);
+        let do_ = d.kvantil(0.9995).min(od + 1000.0);
+        let meje: Vec<f64> = (od as u64..=do_ as u64).map(|k| k as f64).collect();
+        hi_kvadrat_test(vzorec, d, &meje, ocenjeni_parametri)
+    } else {
+        ks_test(vzorec, d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::porazdelitev::Vzorcenje;
+    use crate::{Generator, Normalna, Poisson};
+
+    #[test]
+    fn test_kolmogorov() {
+        // kritična vrednost pri α = 0.05 je 1.358
+        assert!((kolmogorov_sf(1.358) - 0.05).abs() < 1e-3);
+        assert_eq!(kolmogorov_sf(0.01), 1.0);
+    }
+
+    #[test]
+    fn test_prileganje() {
+        let mut g = Generator::new(7);
+        let n = Normalna::new(0.0, 1.0);
+        let vzorec = n.vzorci(&mut g, 1000);
+        assert!(ks_test(&vzorec, &n).p_vrednost > 0.01);
+        assert!(ks_test(&vzorec, &Normalna::new(0.3, 1.0)).p_vrednost < 1e-4);
+
+        let p = Poisson::new(4.0);
+        let vzorec = p.vzorci(&mut g, 1000);
+        let meje: Vec<f64> = (0..15).map(|k| k as f64).collect();
+        let test = hi_kvadrat_test(&vzorec, &p, &meje, 0);
+        assert!(test.p_vrednost > 0.01);
+        assert!(test.stopnje >= 8);
+        assert!(hi_kvadrat_test(&vzorec, &Poisson::new(4.5), &meje, 0).p_vrednost < 1e-3);
+    }
+}
@@ -1,9 +1,23 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::prelude::*;
+
 // f64 je bolj priročna pri porazdelitvah
 // fakulteta ful na ocaml stil
 pub(crate) fn factorial(n: u64) -> f64 {
     (1..=n).fold(1.0, |acc, x| acc * x as f64)
 }
 
+// ln(n!), za velike n s Stirlingovo vrsto, da ne pride do prekoračitve
+pub(crate) fn ln_factorial(n: u64) -> f64 {
+    if n <= 170 {
+        return factorial(n).ln();
+    }
+    let n = n as f64;
+    n * n.ln() - n + 0.5 * (2.0 * core::f64::consts::PI * n).ln() + 1.0 / (12.0 * n)
+        - 1.0 / (360.0 * n.powi(3))
+        + 1.0 / (1260.0 * n.powi(5))
+}
+
 pub(crate) fn binomial(n: u64, k: u64) -> f64 {
     if k > n {
         return 0.0;
@@ -17,6 +31,17 @@ pub(crate) fn binomial(n: u64, k: u64) -> f64 {
     num / denom
 }
 
+// ln(n nad k), za velike n prek ln_factorial, sicer binomial prekorači obseg f64
+pub(crate) fn ln_binomial(n: u64, k: u64) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    if n <= 170 {
+        return binomial(n, k).ln();
+    }
+    ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k)
+}
+
 pub(crate) fn sestej<F>(fun: F, k: u64) -> f64
 where
     F: Fn(u64) -> f64,
@@ -28,19 +53,67 @@ where
     sum
 }
 
+// delne vsote pmf od 0 do največjega floor(x), a največ do meje; vrstni red seštevanja je
+// enak kot v sestej, zato se cdf_batch ujema s cdf
+pub(crate) fn kumulativna_tabela<F>(pmf: F, x: &[f64], meja: u64) -> Vec<f64>
+where
+    F: Fn(u64) -> f64,
+{
+    let zgornja = x
+        .iter()
+        .filter(|x| **x >= 0.0)
+        .map(|x| x.min(meja as f64) as u64)
+        .max();
+    let Some(zgornja) = zgornja else {
+        return Vec::new();
+    };
+
+    let mut sum = 0.0;
+    (0..=zgornja)
+        .map(|i| {
+            sum += pmf(i);
+            sum
+        })
+        .collect()
+}
+
+pub(crate) fn iz_kumulativne(tabela: &[f64], x: &[f64], izhod: &mut [f64]) {
+    assert_eq!(x.len(), izhod.len());
+    for (x, y) in x.iter().zip(izhod.iter_mut()) {
+        *y = if *x >= 0.0 {
+            tabela[(*x as usize).min(tabela.len() - 1)]
+        } else {
+            0.0
+        };
+    }
+}
+
 pub(crate) fn erf(x: f64) -> f64 {
     if x < 0.0 {
-        return erfc_positive(-x) - 1.0;
+        erfc_positive(-x) - 1.0
+    } else {
+        1.0 - erfc_positive(x)
+    }
+}
+
+pub(crate) fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        2.0 - erfc_positive(-x)
     } else {
-        return 1.0 - erfc_positive(x);
+        erfc_positive(x)
     }
 }
 
 // Dia, Yaya D., Approximate Incomplete Integrals,
 //              Application to Complementary Error Function (June 21, 2023).
 //              http://dx.doi.org/10.2139/ssrn.4487559
+#[allow(clippy::excessive_precision)]
 fn erfc_positive(x: f64) -> f64 {
     assert!(x >= 0.0);
+    // erfc(30) < 1e-391 je pod najmanjšim f64, pri večjih x bi x^2 prekoračil obseg
+    if x >= 30.0 {
+        return 0.0;
+    }
     let x2 = x * x;
 
     let term1 = 0.56418958354775629 / (x + 2.06955023132914151);
@@ -58,6 +131,358 @@ fn erfc_positive(x: f64) -> f64 {
     term1 * term2 * term3 * term4 * term5 * term6 * (-x2).exp()
 }
 
+// inverz standardne normalne cdf
+// Acklam, P. J., An algorithm for computing the inverse normal cumulative
+//                distribution function (2003), z enim Halleyjevim popravkom
+#[allow(clippy::excessive_precision)]
+pub(crate) fn probit(p: f64) -> f64 {
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    } else if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.383577518672690e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_NIZKO: f64 = 0.02425;
+
+    let rep = |q: f64| {
+        let t = (-2.0 * q.ln()).sqrt();
+        (((((C[0] * t + C[1]) * t + C[2]) * t + C[3]) * t + C[4]) * t + C[5])
+            / ((((D[0] * t + D[1]) * t + D[2]) * t + D[3]) * t + 1.0)
+    };
+
+    let x = if p < P_NIZKO {
+        rep(p)
+    } else if p > 1.0 - P_NIZKO {
+        -rep(1.0 - p)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+
+    let napaka = 0.5 * erfc(-x / core::f64::consts::SQRT_2) - p;
+    let u = napaka * (2.0 * core::f64::consts::PI).sqrt() * (0.5 * x * x).exp();
+    x - u / (1.0 + 0.5 * x * u)
+}
+
+// najmanjši k >= od, pri katerem vsota pmf od `od` do k doseže p; pod `od` mora biti masa
+// zanemarljiva
+pub(crate) fn kvantil_diskretne<F>(pmf: F, p: f64, od: u64, do_: u64) -> u64
+where
+    F: Fn(u64) -> f64,
+{
+    let mut k = od;
+    let mut vsota = pmf(k);
+    while vsota < p && k < do_ {
+        k += 1;
+        let clen = pmf(k);
+        // mimo glavnine porazdelitve pmf numerično pade na 0 (ali NaN)
+        if (clen.is_nan() || clen <= 0.0) && vsota > 0.5 {
+            break;
+        }
+        vsota += clen;
+    }
+    k
+}
+
+// pod e - 40σ - 40 je masa binomske, Poissonove in hipergeometrijske porazdelitve na dvojno
+// natančnost zanemarljiva (kot nad Poisson::meja), zato vsoto pmf za kvantil začnemo tam in ne
+// pri 0; tako je iskanje dolgo O(σ) namesto O(e)
+pub(crate) fn zacetek_vsote(e: f64, var: f64, od: u64) -> u64 {
+    ((e - 40.0 * var.sqrt() - 40.0).max(0.0) as u64).max(od)
+}
+
+// ničla naraščajoče funkcije f na [lo, hi], kjer f(lo) <= 0 <= f(hi)
+pub(crate) fn bisekcija<F>(f: F, mut lo: f64, mut hi: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    loop {
+        let sredina = 0.5 * (lo + hi);
+        if sredina <= lo || sredina >= hi {
+            return hi;
+        }
+        if f(sredina) < 0.0 {
+            lo = sredina;
+        } else {
+            hi = sredina;
+        }
+    }
+}
+
+// (a * b)[k] = sum_i a[i] b[k - i]; dolge vektorje množimo prek FFT
+pub(crate) fn konvolucija(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let dolzina = a.len() + b.len() - 1;
+    if a.len().min(b.len()) <= 64 {
+        let mut c = vec![0.0; dolzina];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                c[i + j] += x * y;
+            }
+        }
+        return c;
+    }
+
+    let n = dolzina.next_power_of_two();
+    let mut fa: Vec<(f64, f64)> = a.iter().map(|&x| (x, 0.0)).collect();
+    let mut fb: Vec<(f64, f64)> = b.iter().map(|&x| (x, 0.0)).collect();
+    fa.resize(n, (0.0, 0.0));
+    fb.resize(n, (0.0, 0.0));
+    fft(&mut fa, false);
+    fft(&mut fb, false);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x = (x.0 * y.0 - x.1 * y.1, x.0 * y.1 + x.1 * y.0);
+    }
+    fft(&mut fa, true);
+
+    fa[..dolzina].iter().map(|&(re, _)| re / n as f64).collect()
+}
+
+// iterativni radix-2 FFT na kompleksnih številih (re, im), dolžina je potenca 2
+fn fft(x: &mut [(f64, f64)], inverzna: bool) {
+    let n = x.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            x.swap(i, j);
+        }
+    }
+
+    let predznak = if inverzna { 1.0 } else { -1.0 };
+    let mut dolzina = 2;
+    while dolzina <= n {
+        let kot = predznak * 2.0 * core::f64::consts::PI / dolzina as f64;
+        for zacetek in (0..n).step_by(dolzina) {
+            for k in 0..dolzina / 2 {
+                let w = ((kot * k as f64).cos(), (kot * k as f64).sin());
+                let u = x[zacetek + k];
+                let v = x[zacetek + k + dolzina / 2];
+                let v = (v.0 * w.0 - v.1 * w.1, v.0 * w.1 + v.1 * w.0);
+                x[zacetek + k] = (u.0 + v.0, u.1 + v.1);
+                x[zacetek + k + dolzina / 2] = (u.0 - v.0, u.1 - v.1);
+            }
+        }
+        dolzina <<= 1;
+    }
+}
+
+// ln Γ(x) za x > 0, Lanczosov približek (g = 7, n = 9)
+pub(crate) fn ln_gama(x: f64) -> f64 {
+    use core::f64::consts::PI;
+    const G: f64 = 7.0;
+    const C: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    assert!(x > 0.0);
+    if x < 0.5 {
+        // zrcaljenje Γ(x) Γ(1 - x) = π / sin(πx)
+        return (PI / (PI * x).sin()).ln() - ln_gama(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let t = x + G + 0.5;
+    let vsota = C[0] + (1..9).map(|i| C[i] / (x + i as f64)).sum::<f64>();
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + vsota.ln()
+}
+
+// regularizirana zgornja nepopolna gama funkcija Q(a, x) = Γ(a, x) / Γ(a);
+// pri x < a + 1 z vrsto za P = 1 - Q, sicer z verižnim ulomkom (Numerical Recipes)
+pub(crate) fn gama_q(a: f64, x: f64) -> f64 {
+    assert!(a > 0.0 && x >= 0.0);
+    if x == 0.0 {
+        return 1.0;
+    }
+    let predfaktor = (-x + a * x.ln() - ln_gama(a)).exp();
+
+    if x < a + 1.0 {
+        let (mut clen, mut vsota, mut ap) = (1.0 / a, 1.0 / a, a);
+        for _ in 0..1000 {
+            ap += 1.0;
+            clen *= x / ap;
+            vsota += clen;
+            if clen.abs() < vsota.abs() * 1e-16 {
+                break;
+            }
+        }
+        return (1.0 - vsota * predfaktor).max(0.0);
+    }
+
+    // Lentzov algoritem
+    const MAJHNO: f64 = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / MAJHNO;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..1000 {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < MAJHNO {
+            d = MAJHNO;
+        }
+        c = b + an / c;
+        if c.abs() < MAJHNO {
+            c = MAJHNO;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-16 {
+            break;
+        }
+    }
+    (h * predfaktor).min(1.0)
+}
+
+// regularizirana nepopolna beta funkcija I_x(a, b), verižni ulomek (Numerical Recipes);
+// pri x > (a + 1) / (a + b + 2) uporabimo I_x(a, b) = 1 - I_(1-x)(b, a)
+pub(crate) fn beta_i(a: f64, b: f64, x: f64) -> f64 {
+    assert!(a > 0.0 && b > 0.0 && (0.0..=1.0).contains(&x));
+    if x == 0.0 || x == 1.0 {
+        return x;
+    }
+    let predfaktor =
+        (ln_gama(a + b) - ln_gama(a) - ln_gama(b) + a * x.ln() + b * (-x).ln_1p()).exp();
+    if x > (a + 1.0) / (a + b + 2.0) {
+        return 1.0 - predfaktor * beta_ulomek(b, a, 1.0 - x) / b;
+    }
+    predfaktor * beta_ulomek(a, b, x) / a
+}
+
+// Lentzov algoritem za verižni ulomek nepopolne beta funkcije
+fn beta_ulomek(a: f64, b: f64, x: f64) -> f64 {
+    const MAJHNO: f64 = 1e-300;
+    let omeji = |d: f64| if d.abs() < MAJHNO { MAJHNO } else { d };
+    let mut c = 1.0;
+    let mut d = 1.0 / omeji(1.0 - (a + b) * x / (a + 1.0));
+    let mut h = d;
+    for m in 1..1000 {
+        let m = m as f64;
+        // sodi in lihi člen ulomka
+        let clen = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 / omeji(1.0 + clen * d);
+        c = omeji(1.0 + clen / c);
+        h *= d * c;
+        let clen = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 / omeji(1.0 + clen * d);
+        c = omeji(1.0 + clen / c);
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-16 {
+            break;
+        }
+    }
+    h
+}
+
+// določeni integral f na [a, b], meji sta lahko neskončni
+pub(crate) fn integriraj<F>(f: F, a: f64, b: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    if a >= b {
+        return 0.0;
+    }
+    // neskončne vrednosti na robovih substitucije štejemo kot 0
+    let koncna = |y: f64| if y.is_finite() { y } else { 0.0 };
+
+    match (a.is_finite(), b.is_finite()) {
+        (true, true) => simpson(&|x| koncna(f(x)), a, b),
+        (true, false) => simpson(
+            &|t| koncna(f(a + t / (1.0 - t)) / (1.0 - t).powi(2)),
+            0.0,
+            1.0,
+        ),
+        (false, true) => simpson(&|t| koncna(f(b - (1.0 - t) / t) / (t * t)), 0.0, 1.0),
+        (false, false) => simpson(
+            &|t| koncna(f(t / (1.0 - t * t)) * (1.0 + t * t) / (1.0 - t * t).powi(2)),
+            -1.0,
+            1.0,
+        ),
+    }
+}
+
+fn simpson(f: &dyn Fn(f64) -> f64, a: f64, b: f64) -> f64 {
+    let c = 0.5 * (a + b);
+    let (fa, fb, fc) = (f(a), f(b), f(c));
+    let s = (b - a) / 6.0 * (fa + 4.0 * fc + fb);
+    simpson_korak(f, a, b, fa, fb, fc, s, 1e-12, 40)
+}
+
+// prilagodljivo Simpsonovo pravilo
+#[allow(clippy::too_many_arguments)]
+fn simpson_korak(
+    f: &dyn Fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    fa: f64,
+    fb: f64,
+    fc: f64,
+    s: f64,
+    eps: f64,
+    globina: u32,
+) -> f64 {
+    let c = 0.5 * (a + b);
+    let (d, e) = (0.5 * (a + c), 0.5 * (c + b));
+    let (fd, fe) = (f(d), f(e));
+    let levo = (c - a) / 6.0 * (fa + 4.0 * fd + fc);
+    let desno = (b - c) / 6.0 * (fc + 4.0 * fe + fb);
+    let razlika = levo + desno - s;
+
+    if globina == 0 || razlika.abs() <= 15.0 * eps {
+        return levo + desno + razlika / 15.0;
+    }
+    simpson_korak(f, a, c, fa, fc, fd, levo, 0.5 * eps, globina - 1)
+        + simpson_korak(f, c, b, fc, fb, fe, desno, 0.5 * eps, globina - 1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +495,58 @@ mod tests {
         assert_eq!(factorial(10), 3_628_800.);
     }
 
+    #[test]
+    fn test_ln_factorial() {
+        assert_eq!(ln_factorial(0), 0.0);
+        assert!((ln_factorial(10) - factorial(10).ln()).abs() < 1e-12);
+        assert!((ln_factorial(300) - (1..=300).map(|x| (x as f64).ln()).sum::<f64>()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_kumulativna_tabela() {
+        use crate::porazdelitev::*;
+
+        let x = [-1.0, 0.0, 2.5, 7.0, 30.0, 1e12, f64::INFINITY];
+        let mut y = [0.0; 7];
+        for d in [
+            &Poisson::new(3.5) as &dyn Diskretna,
+            &Binomska::new(12, 0.4),
+        ] {
+            d.cdf_batch(&x, &mut y);
+            for (x, y) in x.iter().zip(&y) {
+                assert_eq!(d.cdf(*x), *y);
+            }
+        }
+
+        let n = Normalna::new(1.0, 2.0);
+        n.pdf_batch(&x[..4], &mut y[..4]);
+        for (x, y) in x.iter().zip(&y).take(4) {
+            assert!((n.pdf(*x) - y).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_kvantil_diskretne() {
+        use crate::porazdelitev::*;
+
+        // enako kot vsota od 0
+        let d = Poisson::new(300.0);
+        for p in [0.0, 1e-9, 0.1, 0.5, 0.999] {
+            assert_eq!(
+                d.kvantil(p),
+                kvantil_diskretne(|i| d.pmf(i), p, 0, u64::MAX) as f64
+            );
+        }
+        // pri velikih parametrih ne seštevamo od 0
+        let mediana = Poisson::new(1e7).kvantil(0.5);
+        assert_eq!(mediana, 1e7);
+        let d = Binomska::new(10_000_000, 0.3);
+        assert_eq!(d.kvantil(0.5), 3e6);
+        let k = d.kvantil(0.975);
+        assert!(d.cdf(k - 1.0) < 0.975 && d.cdf(k) >= 0.975);
+        assert!((k - 3e6 - 1.959964 * d.var().sqrt()).abs() < 3.0);
+    }
+
     #[test]
     fn test_binomial_basic() {
         assert_eq!(binomial(5, 0), 1.0); // n choose 0 = 1
@@ -85,4 +562,82 @@ mod tests {
         assert_eq!(binomial(10, 0), 1.0);
         assert_eq!(binomial(10, 10), 1.0);
     }
+
+    #[test]
+    fn test_ln_binomial() {
+        assert_eq!(ln_binomial(5, 6), f64::NEG_INFINITY);
+        assert!((ln_binomial(20, 7) - binomial(20, 7).ln()).abs() < 1e-12);
+        let n = 2000;
+        let vsota = (1..=1000)
+            .map(|i| ((n - 1000 + i) as f64 / i as f64).ln())
+            .sum::<f64>();
+        assert!((ln_binomial(n, 1000) / vsota - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_erfc() {
+        assert_eq!(erfc(f64::MAX), 0.0);
+        assert_eq!(erf(-f64::MAX), -1.0);
+        assert!((erfc(0.0) - 1.0).abs() < 1e-15);
+        assert!((erfc(-1.0) + erfc(1.0) - 2.0).abs() < 1e-15);
+        assert!((erfc(5.0) / 1.537459794428035e-12 - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_probit() {
+        assert!(probit(0.5).abs() < 1e-15);
+        assert!((probit(0.975) - 1.959963984540054).abs() < 1e-12);
+        assert!((probit(1e-20) + 9.262340089798408).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_konvolucija() {
+        let a: Vec<f64> = (0..100).map(|i| (i % 7) as f64).collect();
+        let b: Vec<f64> = (0..80).map(|i| (i % 5) as f64 + 0.5).collect();
+        let mut naivna = vec![0.0; a.len() + b.len() - 1];
+        for i in 0..a.len() {
+            for j in 0..b.len() {
+                naivna[i + j] += a[i] * b[j];
+            }
+        }
+        let c = konvolucija(&a, &b);
+        assert_eq!(c.len(), naivna.len());
+        assert!(c.iter().zip(&naivna).all(|(x, y)| (x - y).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_gama() {
+        // Γ(1/2) = sqrt(π), Γ(5) = 24
+        assert!((ln_gama(0.5) - 0.5 * core::f64::consts::PI.ln()).abs() < 1e-13);
+        assert!((ln_gama(5.0) - 24.0f64.ln()).abs() < 1e-13);
+        // pri a = 1 je Q(1, x) = e^(-x)
+        for x in [0.1, 1.0, 2.5, 30.0] {
+            assert!((gama_q(1.0, x) / (-x).exp() - 1.0).abs() < 1e-12);
+        }
+        // P(χ²_10 > 18.307) = 0.05
+        assert!((gama_q(5.0, 18.307 / 2.0) - 0.05).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_beta() {
+        // I_x(1, 1) = x, I_x(a, 1) = x^a
+        for x in [0.0, 0.1, 0.5, 0.97, 1.0] {
+            assert!((beta_i(1.0, 1.0, x) - x).abs() < 1e-14);
+            assert!((beta_i(2.5, 1.0, x) - x.powf(2.5)).abs() < 1e-14);
+        }
+        // simetrija I_x(a, b) = 1 - I_(1-x)(b, a)
+        assert!((beta_i(3.0, 7.5, 0.2) + beta_i(7.5, 3.0, 0.8) - 1.0).abs() < 1e-14);
+        // I_0.5(5, 5) = 0.5
+        assert!((beta_i(5.0, 5.0, 0.5) - 0.5).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_integriraj() {
+        assert!((integriraj(|x| x * x, 0.0, 3.0) - 9.0).abs() < 1e-12);
+        assert!((integriraj(|x| (-x).exp(), 0.0, f64::INFINITY) - 1.0).abs() < 1e-9);
+        let gauss = |x: f64| (-x * x).exp();
+        let sqrt_pi = core::f64::consts::PI.sqrt();
+        assert!((integriraj(gauss, f64::NEG_INFINITY, f64::INFINITY) - sqrt_pi).abs() < 1e-9);
+        assert!((integriraj(gauss, f64::NEG_INFINITY, 0.0) - 0.5 * sqrt_pi).abs() < 1e-9);
+    }
 }